@@ -0,0 +1,88 @@
+mod parse;
+mod to_query_string;
+
+use crate::filters::{Expr, ParseError, Span};
+use crate::orderby::OrderByItem;
+use crate::pagination::Pagination;
+use crate::select::SelectItem;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub use parse::parse_str;
+pub use to_query_string::{to_query_string, write_query_string};
+
+/// Represents various errors that can occur while parsing an `$expand` option.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ExpandError {
+    /// Error parsing the option or one of its nested options.
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+
+    /// A nested query option was given more than once.
+    #[error("Duplicate nested query option at {span}.")]
+    DuplicateOption { span: Span },
+}
+
+/// Represents a single item of an `$expand` option.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExpandItem {
+    /// The navigation properties to expand.
+    pub target: ExpandTarget,
+
+    /// The query options nested within parentheses.
+    pub options: ExpandOptions,
+}
+
+/// Represents the navigation properties an `ExpandItem` applies to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExpandTarget {
+    /// All navigation properties, written as `*`.
+    Wildcard,
+
+    /// A navigation property path made of one or more segments, such as `Customer/Orders`.
+    Path(Vec<String>),
+}
+
+/// Represents the query options nested within an `ExpandItem`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExpandOptions {
+    /// The nested `$filter` option.
+    pub filter: Option<Expr>,
+
+    /// The nested `$orderby` option.
+    pub order_by: Option<Vec<OrderByItem>>,
+
+    /// The nested `$select` option.
+    pub select: Option<Vec<SelectItem>>,
+
+    /// The nested `$expand` option.
+    pub expand: Option<Vec<ExpandItem>>,
+
+    /// The nested `$top`, `$skip` and `$count` options.
+    pub pagination: Pagination,
+
+    /// The nested `$levels` option.
+    pub levels: Option<Levels>,
+}
+
+/// Represents the `$levels` option of a recursive expansion.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Levels {
+    /// A fixed number of levels.
+    Depth(u64),
+
+    /// As many levels as exist, written as `max`.
+    Max,
+}
+
+impl ExpandOptions {
+    /// Checks if no nested options were given.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
@@ -0,0 +1,173 @@
+use super::{ExpandError, ExpandItem, ExpandOptions, Levels};
+use crate::filters::parse::EmbeddedErrors;
+use crate::filters::{Expr, Span};
+use crate::orderby::OrderByItem;
+use crate::select::SelectItem;
+
+/// Parses an OData v4 `$expand` option string into a tree of `ExpandItem`.
+///
+/// Nested `$filter`, `$orderby` and `$select` options are parsed with the
+/// same grammars as their top level counterparts.
+///
+/// ```
+/// use odata_params::expand::{parse_str, ExpandTarget};
+///
+/// let expand = "Orders($filter=Total gt 100;$orderby=Date desc;$top=5;$expand=Items)";
+/// let result = parse_str(expand).expect("valid expand tree");
+///
+/// assert_eq!(result[0].target, ExpandTarget::Path(vec!["Orders".to_owned()]));
+/// assert_eq!(result[0].options.pagination.top, Some(5));
+/// assert!(result[0].options.filter.is_some());
+/// ```
+pub fn parse_str(query: impl AsRef<str>) -> Result<Vec<ExpandItem>, ExpandError> {
+    let query = query.as_ref();
+    let errors = EmbeddedErrors::default();
+
+    match odata_expand::parse_str(query, &errors) {
+        Ok(items) => items,
+        Err(error) => Err(errors.syntax_error(query, error).into()),
+    }
+}
+
+/// A single option nested within an `$expand` item.
+enum ExpandOption {
+    Filter(Expr),
+    OrderBy(Vec<OrderByItem>),
+    Select(Vec<SelectItem>),
+    Expand(Vec<ExpandItem>),
+    Top(u64),
+    Skip(u64),
+    Count(bool),
+    Levels(Levels),
+}
+
+/// Combines nested `$expand` options with their spans, returning an error
+/// locating the first option that is repeated.
+fn combine_expand_options(list: Vec<(ExpandOption, Span)>) -> Result<ExpandOptions, ExpandError> {
+    let mut options = ExpandOptions::default();
+    let mut has_count = false;
+
+    for (option, span) in list {
+        let is_repeated = match option {
+            ExpandOption::Filter(e) => options.filter.replace(e).is_some(),
+            ExpandOption::OrderBy(o) => options.order_by.replace(o).is_some(),
+            ExpandOption::Select(s) => options.select.replace(s).is_some(),
+            ExpandOption::Expand(e) => options.expand.replace(e).is_some(),
+            ExpandOption::Top(n) => options.pagination.top.replace(n).is_some(),
+            ExpandOption::Skip(n) => options.pagination.skip.replace(n).is_some(),
+            ExpandOption::Count(b) => {
+                options.pagination.count = b;
+                std::mem::replace(&mut has_count, true)
+            }
+            ExpandOption::Levels(l) => options.levels.replace(l).is_some(),
+        };
+
+        if is_repeated {
+            return Err(ExpandError::DuplicateOption { span });
+        }
+    }
+
+    Ok(options)
+}
+
+peg::parser! {
+    /// Parses OData v4 `$expand` options, where nested options are parsed
+    /// with the grammars of `$filter`, `$orderby` and `$select`.
+    pub(crate) grammar odata_expand(errors: &EmbeddedErrors) for str {
+        use super::{combine_expand_options, ExpandOption};
+        use crate::expand::{ExpandError, ExpandItem, ExpandOptions, ExpandTarget, Levels};
+        use crate::filters::parse::Spanned;
+        use crate::filters::{ParseError, Span};
+        use crate::orderby::parse::odata_order_by;
+        use crate::orderby::OrderByItem;
+        use crate::select::parse::odata_select;
+        use crate::select::SelectItem;
+
+        /// Entry point for parsing an `$expand` option string.
+        pub(crate) rule parse_str() -> Result<Vec<ExpandItem>, ExpandError>
+            = expand()
+
+        /// Parses a list of expand items separated by commas.
+        rule expand() -> Result<Vec<ExpandItem>, ExpandError>
+            = _ l:expand_item() ++ ( _ "," _ ) _ { l.into_iter().collect() }
+
+        /// Parses a single expand item with its optional nested query options.
+        rule expand_item() -> Result<ExpandItem, ExpandError>
+            = t:expand_target() o:(_ "(" _ o:expand_options() _ ")" { o })? {
+                Ok(ExpandItem { target: t, options: o.transpose()?.unwrap_or_default() })
+            }
+
+        /// Parses the navigation properties to expand.
+        rule expand_target() -> ExpandTarget
+            = "*" { ExpandTarget::Wildcard }
+            / p:identifier() ++ "/" { ExpandTarget::Path(p) }
+
+        /// Parses a list of nested query options separated by semicolons.
+        rule expand_options() -> Result<ExpandOptions, ExpandError>
+            = l:(s:position!() o:expand_option() e:position!() { o.map(|o| (o, Span { start: s, end: e })) }) ++ ( _ ";" _ ) {
+                combine_expand_options(l.into_iter().collect::<Result<Vec<_>, _>>()?)
+            }
+
+        /// Parses a single nested query option.
+        rule expand_option() -> Result<ExpandOption, ExpandError>
+            = option_name("filter") e:filter() { Ok(ExpandOption::Filter(e?.0)) }
+            / option_name("orderby") o:order_by() { Ok(ExpandOption::OrderBy(o?)) }
+            / option_name("select") s:select() { Ok(ExpandOption::Select(s)) }
+            / option_name("expand") e:expand() { Ok(ExpandOption::Expand(e?)) }
+            / option_name("top") n:integer() { Ok(ExpandOption::Top(n)) }
+            / option_name("skip") n:integer() { Ok(ExpandOption::Skip(n)) }
+            / option_name("count") b:(quiet!{ bool_value() } / expected!("boolean")) { Ok(ExpandOption::Count(b)) }
+            / option_name("levels") l:levels() { Ok(ExpandOption::Levels(l)) }
+
+        /// Parses a nested `$filter` option with the grammar of `$filter`.
+        rule filter() -> Spanned
+            = #{|input, pos| errors.expression(input, pos)}
+
+        /// Parses a nested `$orderby` option with the grammar of `$orderby`.
+        rule order_by() -> Result<Vec<OrderByItem>, ParseError>
+            = #{|input, pos| errors.embed(|embedded| odata_order_by::nested(input, errors, pos, embedded))}
+
+        /// Parses a nested `$select` option with the grammar of `$select`.
+        rule select() -> Vec<SelectItem>
+            = #{|input, pos| errors.embed(|embedded| odata_select::nested(input, pos, embedded))}
+
+        /// Matches a query option name with an optional `$` prefix followed
+        /// by an equals sign, ignoring the case of the name.
+        rule option_name(name: &'static str)
+            = quiet!{ "$"? keyword(name) _ "=" } _
+            / expected!("a nested query option")
+
+        /// Matches a word equal to the given keyword, ignoring its case.
+        rule keyword(word: &'static str)
+            = quiet!{
+                w:$(['a'..='z'|'A'..='Z']+) {?
+                    if w.eq_ignore_ascii_case(word) { Ok(()) } else { Err(word) }
+                }
+            }
+            / expected!(word)
+
+        /// Parses the value of a `$levels` option.
+        rule levels() -> Levels
+            = keyword("max") { Levels::Max }
+            / n:integer() { Levels::Depth(n) }
+
+        /// Parses a non-negative integer.
+        rule integer() -> u64
+            = quiet!{ n:$(['0'..='9']+) {? n.parse().or(Err("integer")) } }
+            / expected!("integer")
+
+        /// Parses a boolean value, ignoring its case.
+        rule bool_value() -> bool
+            = ['t'|'T']['r'|'R']['u'|'U']['e'|'E'] { true }
+            / ['f'|'F']['a'|'A']['l'|'L']['s'|'S']['e'|'E'] { false }
+
+        /// Parses an identifier.
+        rule identifier() -> String
+            = quiet!{ s:$(['a'..='z'|'A'..='Z'|'_']['a'..='z'|'A'..='Z'|'_'|'0'..='9']*) { s.to_string() } }
+            / expected!("identifier")
+
+        /// Matches zero or more whitespace characters.
+        rule _()
+            = quiet!{ [' '|'\t'|'\n'|'\r']* }
+    }
+}
@@ -0,0 +1,108 @@
+use super::{ExpandItem, ExpandOptions, ExpandTarget, Levels};
+use crate::{filters, orderby, select};
+use std::fmt::{self, Write};
+
+/// Converts a list of `ExpandItem` to its corresponding OData `$expand` string representation.
+///
+/// A `Result` containing the resulting query string or a `fmt::Error`.
+///
+/// ```
+/// use odata_params::expand::{to_query_string, ExpandItem, ExpandOptions, ExpandTarget};
+/// use odata_params::pagination::Pagination;
+///
+/// let items = vec![ExpandItem {
+///     target: ExpandTarget::Path(vec!["Orders".to_owned()]),
+///     options: ExpandOptions {
+///         pagination: Pagination {
+///             top: Some(5),
+///             ..Default::default()
+///         },
+///         ..Default::default()
+///     },
+/// }];
+///
+/// let query_string = to_query_string(&items).expect("valid expand tree");
+///
+/// assert_eq!(query_string, "Orders($top=5)");
+/// ```
+pub fn to_query_string(items: &[ExpandItem]) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+    write_query_string(&mut output, items)?;
+    Ok(output)
+}
+
+/// Writes a list of `ExpandItem` to a writer as its corresponding OData `$expand` string representation.
+///
+/// A `fmt::Result` indicating the success or failure of the write operation.
+pub fn write_query_string<W: Write>(writer: &mut W, items: &[ExpandItem]) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            write!(writer, ",")?;
+        }
+
+        match &item.target {
+            ExpandTarget::Wildcard => write!(writer, "*")?,
+            ExpandTarget::Path(segments) => write!(writer, "{}", segments.join("/"))?,
+        }
+
+        if !item.options.is_empty() {
+            write!(writer, "(")?;
+            write_options(writer, &item.options)?;
+            write!(writer, ")")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the nested options of an `ExpandItem` separated by semicolons.
+///
+/// A `fmt::Result` indicating the success or failure of the write operation.
+fn write_options<W: Write>(writer: &mut W, options: &ExpandOptions) -> fmt::Result {
+    let mut separator = "";
+
+    if let Some(filter) = &options.filter {
+        write!(writer, "{separator}$filter=")?;
+        filters::write_query_string(writer, filter)?;
+        separator = ";";
+    }
+
+    if let Some(order_by) = &options.order_by {
+        write!(writer, "{separator}$orderby=")?;
+        orderby::write_query_string(writer, order_by)?;
+        separator = ";";
+    }
+
+    if let Some(select) = &options.select {
+        write!(writer, "{separator}$select=")?;
+        select::write_query_string(writer, select)?;
+        separator = ";";
+    }
+
+    if let Some(expand) = &options.expand {
+        write!(writer, "{separator}$expand=")?;
+        write_query_string(writer, expand)?;
+        separator = ";";
+    }
+
+    if let Some(top) = options.pagination.top {
+        write!(writer, "{separator}$top={top}")?;
+        separator = ";";
+    }
+
+    if let Some(skip) = options.pagination.skip {
+        write!(writer, "{separator}$skip={skip}")?;
+        separator = ";";
+    }
+
+    if options.pagination.count {
+        write!(writer, "{separator}$count=true")?;
+        separator = ";";
+    }
+
+    match options.levels {
+        Some(Levels::Depth(depth)) => write!(writer, "{separator}$levels={depth}"),
+        Some(Levels::Max) => write!(writer, "{separator}$levels=max"),
+        None => Ok(()),
+    }
+}
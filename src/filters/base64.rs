@@ -0,0 +1,60 @@
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encodes bytes as base64url with padding.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, byte)| {
+            group | (*byte as u32) << (16 - 8 * i)
+        });
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(group >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+/// Decodes base64url with optional padding, or `None` if the input is not
+/// strictly valid, including when unused trailing bits are not zero.
+pub(crate) fn decode(encoded: &str) -> Option<Vec<u8>> {
+    let data = encoded.trim_end_matches('=');
+    let padding = encoded.len() - data.len();
+
+    // Padding, when given, completes the last group of four characters.
+    if data.len() % 4 == 1 || (padding > 0 && (padding > 2 || data.len() % 4 + padding != 4)) {
+        return None;
+    }
+
+    let values = data
+        .bytes()
+        .map(|c| ALPHABET.iter().position(|a| *a == c).map(|v| v as u32))
+        .collect::<Option<Vec<_>>>()?;
+
+    let mut bytes = Vec::with_capacity(values.len() * 3 / 4);
+
+    for chunk in values.chunks(4) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |group, (i, value)| group | value << (18 - 6 * i));
+
+        // A partial group holds one byte less than its number of characters.
+        let byte_count = chunk.len() - 1;
+
+        // Bits beyond the last byte must be zero for the encoding to be canonical.
+        if group & (0xFF_FFFF >> (8 * byte_count)) != 0 {
+            return None;
+        }
+
+        bytes.extend((0..byte_count).map(|i| (group >> (16 - 8 * i)) as u8));
+    }
+
+    Some(bytes)
+}
@@ -1,16 +1,20 @@
-mod parse;
+mod base64;
+pub(crate) mod parse;
+mod resolve;
+mod standard_functions;
 mod to_query_string;
 mod validate;
 
 use bigdecimal::BigDecimal;
-use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, TimeDelta};
 #[cfg(feature = "serde")]
-use serde::{Serialize, Deserialize};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use thiserror::Error;
 use uuid::Uuid;
 
-pub use parse::parse_str;
+pub use parse::{parse_str, parse_str_with_spans};
+pub use resolve::resolve_aliases;
 pub use to_query_string::{to_query_string, write_query_string};
 
 /// This alias is to make the rename to ParseError a non-breaking change.
@@ -21,41 +25,144 @@ pub use ParseError as Error;
 /// Represents various errors that can occur during parsing.
 #[derive(Clone, Debug, Error, PartialEq, Eq)]
 pub enum ParseError {
-    /// Error during general parsing.
-    #[error("Error during general parsing.")]
-    Parsing,
+    /// Error during general parsing, with the location where parsing
+    /// failed and the tokens that were expected there.
+    ///
+    /// The `offset` is in bytes while the `line` and `column` start at 1.
+    /// The `snippet` is the line of the source containing the failing spot.
+    #[error(
+        "Error during general parsing at line {line}, column {column}, expected {}.\n{}",
+        expected_tokens(expected),
+        caret(snippet, *column)
+    )]
+    Parsing {
+        offset: usize,
+        line: usize,
+        column: usize,
+        snippet: String,
+        expected: Vec<String>,
+    },
 
     /// Error parsing a UUID.
-    #[error("Error parsing a UUID.")]
-    ParsingUuid,
+    #[error("Error parsing a UUID at {span}.")]
+    ParsingUuid { span: Span },
 
     /// Error parsing a number.
-    #[error("Error parsing a number.")]
-    ParsingNumber,
+    #[error("Error parsing a number at {span}.")]
+    ParsingNumber { span: Span },
 
     /// Error parsing a date.
-    #[error("Error parsing a date.")]
-    ParsingDate,
+    #[error("Error parsing a date at {span}.")]
+    ParsingDate { span: Span },
 
     /// Error parsing a time.
-    #[error("Error parsing a time.")]
-    ParsingTime,
+    #[error("Error parsing a time at {span}.")]
+    ParsingTime { span: Span },
 
     /// Error parsing a datetime.
-    #[error("Error parsing a date and time.")]
-    ParsingDateTime,
+    #[error("Error parsing a date and time at {span}.")]
+    ParsingDateTime { span: Span },
+
+    /// Error parsing a base64url binary value.
+    #[error("Error parsing a binary value at {span}.")]
+    ParsingBinary { span: Span },
+
+    /// Error parsing a geography or geometry value.
+    #[error("Error parsing a spatial value at {span}.")]
+    ParsingSpatial { span: Span },
+
+    /// Error parsing a duration.
+    #[error("Error parsing a duration at {span}.")]
+    ParsingDuration { span: Span },
 
     /// Error parsing a time zone offset.
-    #[error("Error parsing a time zone offset.")]
-    ParsingTimeZone,
+    #[error("Error parsing a time zone offset at {span}.")]
+    ParsingTimeZone { span: Span },
 
     /// Error parsing a named time zone.
-    #[error("Error parsing a named time zone.")]
-    ParsingTimeZoneNamed,
+    #[error("Error parsing a named time zone at {span}.")]
+    ParsingTimeZoneNamed { span: Span },
 
     /// Error parsing a Unicode code point escape sequence.
-    #[error("Error parsing a Unicode code point escape sequence.")]
-    ParsingUnicodeCodePoint,
+    #[error("Error parsing a Unicode code point escape sequence at {span}.")]
+    ParsingUnicodeCodePoint { span: Span },
+}
+
+/// Lists the tokens expected by the parser for an error message.
+fn expected_tokens(expected: &[String]) -> String {
+    match expected {
+        [] => "nothing".to_owned(),
+        [token] => token.to_owned(),
+        tokens => format!("one of {}", tokens.join(", ")),
+    }
+}
+
+/// Renders a line of source with a caret under the given column.
+fn caret(snippet: &str, column: usize) -> String {
+    format!("{snippet}\n{}^", " ".repeat(column.saturating_sub(1)))
+}
+
+/// Represents a range of byte offsets within a parsed string.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Span {
+    /// Offset of the first byte.
+    pub start: usize,
+
+    /// Offset just past the last byte.
+    pub end: usize,
+}
+
+/// Converts a `Span` to its string representation.
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// Represents the source locations of an `Expr` node and of its children.
+///
+/// The `children` follow the order of the child expressions of the node:
+/// both operands of a binary operator, the operand of a unary operator,
+/// the operand then each listed value of `in` or its collection, the
+/// collection then the predicate of a lambda, and the arguments of a
+/// function call.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExprSpans {
+    /// Location of the whole expression, including any enclosing parentheses.
+    pub span: Span,
+
+    /// Location of the operator keyword or function name, if any.
+    pub operator: Option<Span>,
+
+    /// Locations of the child expressions.
+    pub children: Vec<ExprSpans>,
+}
+
+impl ExprSpans {
+    /// Returns the locations of the node reached by following the given
+    /// child indices from this node.
+    pub fn get(&self, path: &[usize]) -> Option<&ExprSpans> {
+        path.iter()
+            .try_fold(self, |spans, &index| spans.children.get(index))
+    }
+}
+
+/// Represents various errors that can occur while resolving parameter aliases.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum AliasError {
+    /// No value was given for a parameter alias.
+    #[error("Undefined parameter alias '@{name}'.")]
+    UndefinedAlias { name: String },
+
+    /// A parameter alias refers back to itself through its value.
+    #[error("Parameter alias '@{name}' refers to itself.")]
+    CyclicAlias { name: String },
+
+    /// The value of a parameter alias could not be parsed.
+    #[error("Invalid value for parameter alias '@{name}': {error}")]
+    InvalidAliasValue { name: String, error: ParseError },
 }
 
 #[derive(Clone, Debug, Error, PartialEq, Eq)]
@@ -68,14 +175,99 @@ pub enum ValidationError {
     #[error("Logical NOT requires a boolean operand but got {given:?}.")]
     LogicalNotRequiresBoolean { given: Type },
 
+    /// Arithmetic negation requires a numeric operand.
+    #[error("Negation requires a numeric operand but got {given:?}.")]
+    NegationRequiresNumber { given: Type },
+
     /// Comparison between incompatible types.
     #[error("Comparing incompatible types: lhs = {lhs:?}, rhs = {rhs:?}.")]
     ComparingIncompatibleTypes { lhs: Type, rhs: Type },
 
+    /// A number literal does not fit the type it is used as.
+    #[error("Number {value} is out of range for type {expected:?}.")]
+    NumberOutOfRange { value: BigDecimal, expected: Type },
+
+    /// Ordering comparisons on a type that only supports equality.
+    #[error("Comparison operator '{operator}' is not supported for type {given:?}.")]
+    UnsupportedComparisonOperator {
+        operator: CompareOperator,
+        given: Type,
+    },
+
+    /// Only nullable values can be compared with `null`.
+    #[error("Comparing null with non-nullable type {given:?}.")]
+    ComparingNullWithNonNullable { given: Type },
+
+    /// Arithmetic between types that do not support it.
+    #[error("Arithmetic operator '{operator}' is not supported between types: lhs = {lhs:?}, rhs = {rhs:?}.")]
+    ArithmeticOnIncompatibleTypes {
+        operator: ArithmeticOperator,
+        lhs: Type,
+        rhs: Type,
+    },
+
+    /// The has operator requires enum operands of the same type.
+    #[error("The has operator requires enums of the same type: lhs = {lhs:?}, rhs = {rhs:?}.")]
+    HasRequiresMatchingEnums { lhs: Type, rhs: Type },
+
+    /// Undefined enum type.
+    #[error("Undefined enum type '{name}'.")]
+    UndefinedEnumType { name: String },
+
+    /// Undefined enum member.
+    #[error("Undefined member '{member}' of enum type '{type_name}'.")]
+    UndefinedEnumMember { type_name: String, member: String },
+
+    /// The in operator requires a collection when not given a list.
+    #[error("The in operator requires a collection but got {given:?}.")]
+    InRequiresCollection { given: Type },
+
+    /// Lambda operators require a collection operand.
+    #[error("Lambda operator '{operator}' requires a collection but got {given:?}.")]
+    LambdaRequiresCollection {
+        operator: LambdaOperator,
+        given: Type,
+    },
+
+    /// Lambda predicates must be boolean expressions.
+    #[error("Lambda operator '{operator}' requires a boolean predicate but got {given:?}.")]
+    LambdaRequiresBooleanPredicate {
+        operator: LambdaOperator,
+        given: Type,
+    },
+
+    /// Elements of a collection value must share a type.
+    #[error("Collection elements must share a type: expected {expected:?} but got {given:?}.")]
+    IncompatibleCollectionElements { expected: Type, given: Type },
+
+    /// Undefined type given to `cast` or `isof`.
+    #[error("Undefined type '{name}'.")]
+    UndefinedType { name: String },
+
+    /// Type names can only be given as the last argument of `cast` or `isof`.
+    #[error("Unexpected type name '{name}' outside of cast or isof.")]
+    UnexpectedTypeName { name: String },
+
+    /// Parameter aliases must be resolved before validation.
+    #[error("Unresolved parameter alias '@{name}'.")]
+    UnresolvedAlias { name: String },
+
     /// Undefined identifier.
     #[error("Undefined identifier '{name}'.")]
     UndefinedIdentifier { name: String },
 
+    /// Undefined complex type.
+    #[error("Undefined complex type '{name}'.")]
+    UndefinedComplexType { name: String },
+
+    /// Undefined property of a complex type.
+    #[error("Undefined property '{property}' of complex type '{type_name}'.")]
+    UndefinedProperty { type_name: String, property: String },
+
+    /// Member paths can only continue through complex types.
+    #[error("Property '{property}' cannot be accessed on type {given:?}.")]
+    PropertyAccessRequiresComplexType { property: String, given: Type },
+
     /// Undefined function.
     #[error("Undefined function '{name}'.")]
     UndefinedFunction { name: String },
@@ -100,6 +292,42 @@ pub enum ValidationError {
         expected: Type,
         given: Type,
     },
+
+    /// None of the overloads of a function accept the given arguments.
+    #[error(
+        "No overload of function '{name}' accepts arguments {given:?}, candidates are: {}.",
+        candidates.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+    )]
+    NoMatchingFunctionOverload {
+        name: String,
+        given: Vec<Type>,
+        candidates: Vec<FunctionSignature>,
+    },
+}
+
+/// A validation error along with the location of the expression that caused it.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[error("{error} (at {span})")]
+pub struct SpannedValidationError {
+    /// The validation error.
+    pub error: ValidationError,
+
+    /// Location of the expression that caused the error.
+    pub span: Span,
+}
+
+/// A validation error along with the path to the expression that caused it.
+///
+/// The path lists child indices from the root expression in the same order
+/// as `ExprSpans::children`, so it can be given to `ExprSpans::get`.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[error("{error} (at node {path:?})")]
+pub struct NodeValidationError {
+    /// Child indices leading from the root to the offending expression.
+    pub path: Vec<usize>,
+
+    /// The validation error.
+    pub error: ValidationError,
 }
 
 /// Represents the different types of expressions in the AST.
@@ -118,21 +346,57 @@ pub enum Expr {
     /// Comparison between two expressions.
     Compare(Box<Expr>, CompareOperator, Box<Expr>),
 
+    /// Arithmetic operation between two expressions.
+    Arithmetic(Box<Expr>, ArithmeticOperator, Box<Expr>),
+
+    /// Arithmetic negation of a numeric expression.
+    Negate(Box<Expr>),
+
+    /// Has operator to check if an enum value has the given flags set.
+    Has(Box<Expr>, Box<Expr>),
+
     /// In operator to check if a value is within a list of values.
+    ///
+    /// The list can be given in parentheses, `Name in ('a', 'b')`, or as a
+    /// collection literal, `Name in ['a','b']`, which are parsed the same
+    /// and always written back in parentheses.
     In(Box<Expr>, Vec<Expr>),
 
+    /// In operator to check if a value is a member of a collection given by
+    /// an expression, such as `Name in @names` or `Name in Tags`.
+    ///
+    /// A parameter alias whose value is a collection literal is expanded
+    /// into an `In` list when aliases are resolved.
+    InCollection(Box<Expr>, Box<Expr>),
+
+    /// Lambda operator applied to a collection, with an optional range
+    /// variable name and the predicate it is evaluated against.
+    ///
+    /// Only `any` may omit the range variable and predicate.
+    Lambda(Box<Expr>, LambdaOperator, Option<(String, Box<Expr>)>),
+
     /// Function call with a name and a list of arguments.
     Function(String, Vec<Expr>),
 
+    /// A parameter alias, such as `@p`, named without its `@` prefix.
+    Alias(String),
+
+    /// A qualified type name, such as `Edm.Int32`, given as the last
+    /// argument of the type functions `cast` and `isof`.
+    TypeName(String),
+
     /// An identifier.
     Identifier(String),
 
+    /// A member path of two or more segments, such as `Address/City`.
+    Path(Vec<String>),
+
     /// A constant value.
     Value(Value),
 }
 
 /// Represents the various comparison operators.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum CompareOperator {
     /// Equal to.
@@ -168,6 +432,65 @@ impl std::fmt::Display for CompareOperator {
     }
 }
 
+/// Represents the lambda operators over collections.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LambdaOperator {
+    /// True if the predicate holds for any member, or if the collection
+    /// is not empty when there is no predicate.
+    Any,
+
+    /// True if the predicate holds for all members.
+    All,
+}
+
+/// Converts a `LambdaOperator` to its string representation.
+impl std::fmt::Display for LambdaOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LambdaOperator::Any => write!(f, "any"),
+            LambdaOperator::All => write!(f, "all"),
+        }
+    }
+}
+
+/// Represents the various arithmetic operators.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ArithmeticOperator {
+    /// Addition.
+    Add,
+
+    /// Subtraction.
+    Sub,
+
+    /// Multiplication.
+    Mul,
+
+    /// Division.
+    Div,
+
+    /// Decimal division, even for integer operands.
+    DivBy,
+
+    /// Modulo.
+    Mod,
+}
+
+/// Converts an `ArithmeticOperator` to its string representation.
+impl std::fmt::Display for ArithmeticOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArithmeticOperator::Add => write!(f, "add"),
+            ArithmeticOperator::Sub => write!(f, "sub"),
+            ArithmeticOperator::Mul => write!(f, "mul"),
+            ArithmeticOperator::Div => write!(f, "div"),
+            ArithmeticOperator::DivBy => write!(f, "divby"),
+            ArithmeticOperator::Mod => write!(f, "mod"),
+        }
+    }
+}
+
 /// Represents the various value types.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -178,14 +501,39 @@ pub enum Value {
     /// Boolean value.
     Bool(bool),
 
-    /// Numeric value.
+    /// Numeric value written without a suffix or exponent, typed as the
+    /// smallest of `Int32`, `Int64` or `Decimal` that holds it.
     Number(BigDecimal),
 
+    /// 64-bit integer value written with an `L` suffix, such as `42L`.
+    Int64(i64),
+
+    /// Decimal value written with an `m` suffix, such as `4.5m`.
+    Decimal(BigDecimal),
+
+    /// Double precision value written with an exponent or a `d` suffix,
+    /// such as `1.5e3` or `4.5d`.
+    Double(BigDecimal),
+
+    /// Single precision value written with an `f` suffix, such as `4.5f`.
+    Single(BigDecimal),
+
+    /// Positive infinity written as `INF`.
+    Infinity,
+
+    /// Negative infinity written as `-INF`.
+    NegativeInfinity,
+
+    /// Not a number written as `NaN`.
+    NaN,
+
     /// Unique ID sometimes referred to as GUIDs.
     Uuid(Uuid),
 
-    /// Date and time with time zone value.
-    DateTime(DateTime<Utc>),
+    /// Date and time value with the offset it was written with, along with
+    /// the named time zone when it was written with one, such as
+    /// `2024-03-01T10:00:00America/Toronto`.
+    DateTime(DateTime<FixedOffset>, Option<chrono_tz::Tz>),
 
     /// Date value.
     Date(NaiveDate),
@@ -193,29 +541,138 @@ pub enum Value {
     /// Time value.
     Time(NaiveTime),
 
+    /// Duration value of days, hours, minutes and seconds.
+    ///
+    /// Bare durations, such as `P1D`, take precedence over identifiers of
+    /// the same name.
+    Duration(TimeDelta),
+
     /// String value.
     String(String),
+
+    /// Binary value written as base64url, such as `binary'T0RhdGE='`.
+    Binary(Vec<u8>),
+
+    /// Enum value with its qualified type name and one or more member names.
+    ///
+    /// Multiple members represent a combination of flags.
+    Enum {
+        type_name: String,
+        members: Vec<String>,
+    },
+
+    /// Geography value on a round earth, such as
+    /// `geography'SRID=4326;POINT(-73.5 45.5)'`.
+    Geography(Spatial),
+
+    /// Geometry value on a flat plane, such as `geometry'POINT(1 2)'`.
+    Geometry(Spatial),
+
+    /// Collection value written as a JSON array, such as `[1,2,3]`.
+    Collection(Vec<Value>),
+
+    /// Complex value written as a JSON object, such as `{"City":"Montreal"}`,
+    /// with its members in the order they were given.
+    Complex(Vec<(String, Value)>),
+}
+
+/// A spatial value written as well-known text, with an optional spatial
+/// reference system identifier.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Spatial {
+    /// Spatial reference system identifier given with `SRID=4326;`.
+    pub srid: Option<u32>,
+
+    /// The shape of the value.
+    pub shape: Shape,
+}
+
+/// Represents the shapes of spatial values.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Shape {
+    /// A single position, `POINT(1 2)`.
+    Point(Position),
+
+    /// Two or more connected positions, `LINESTRING(1 2, 3 4)`.
+    LineString(Vec<Position>),
+
+    /// An outer ring followed by any holes, where every ring is closed by
+    /// ending on its first position, `POLYGON((0 0, 4 0, 4 4, 0 0))`.
+    Polygon(Vec<Vec<Position>>),
+
+    /// Any number of points, `MULTIPOINT((1 2), (3 4))`.
+    MultiPoint(Vec<Position>),
+
+    /// Any number of line strings, `MULTILINESTRING((1 2, 3 4), (5 6, 7 8))`.
+    MultiLineString(Vec<Vec<Position>>),
+
+    /// Any number of polygons, `MULTIPOLYGON(((0 0, 4 0, 4 4, 0 0)))`.
+    MultiPolygon(Vec<Vec<Vec<Position>>>),
+
+    /// Any number of shapes, `COLLECTION(POINT(1 2), LINESTRING(1 2, 3 4))`.
+    Collection(Vec<Shape>),
+}
+
+/// A position within a spatial value, such as longitude and latitude.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Position {
+    pub x: BigDecimal,
+    pub y: BigDecimal,
 }
 
-#[derive(Copy, Clone, Debug, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Type {
+    /// The type of the `null` literal.
     Null,
     Boolean,
-    Number,
+    Byte,
+    SByte,
+    Int32,
+    Int64,
+    Decimal,
+    Single,
+    Double,
     Uuid,
     DateTime,
     Date,
     Time,
+    Duration,
     String,
+    Binary,
+    /// A spatial type on a round earth, such as `geography'POINT(1 2)'`.
+    Geography,
+    /// A spatial type on a flat plane, such as `geometry'POINT(1 2)'`.
+    Geometry,
+    /// An enum type identified by its qualified type name.
+    Enum(String),
+    /// A complex or entity type identified by its qualified type name.
+    Complex(String),
+    /// The type of a complex literal with the types of its members, which
+    /// converts to any declared complex type with compatible properties.
+    Object(Vec<(String, Type)>),
+    /// A collection with members of the inner type.
+    Collection(Box<Type>),
+    /// A declared type whose values can also be `null`.
+    Nullable(Box<Type>),
 }
 
-impl PartialEq for Type {
-    fn eq(&self, other: &Self) -> bool {
-        use core::mem::discriminant as variant;
-
-        variant(self) == variant(other)
-            || variant(other) == variant(&Type::Null)
-            || variant(self) == variant(&Type::Null)
+impl Type {
+    /// Marks the type as nullable, allowing it to be compared with `null`.
+    ///
+    /// ```
+    /// use odata_params::filters::Type;
+    ///
+    /// assert_eq!(Type::String.nullable(), Type::Nullable(Box::new(Type::String)));
+    /// assert_eq!(Type::String.nullable().nullable(), Type::String.nullable());
+    /// ```
+    pub fn nullable(self) -> Self {
+        match self {
+            Type::Null | Type::Nullable(_) => self,
+            _ => Type::Nullable(Box::new(self)),
+        }
     }
 }
 
@@ -226,11 +683,59 @@ impl PartialEq for Type {
 /// use odata_params::filters::{IdentifiersTypeMap, Type};
 ///
 /// let mut map = HashMap::new();
-/// map.insert("x".to_string(), Type::Number);
+/// map.insert("x".to_string(), Type::Int32);
 ///
 /// let identifiers_map: IdentifiersTypeMap = map.into();
 /// ```
-pub struct IdentifiersTypeMap(HashMap<String, Type>);
+///
+/// Enum types referenced by identifiers or enum literals are declared
+/// along with their member names.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use odata_params::filters::{IdentifiersTypeMap, Type};
+///
+/// let mut map = HashMap::new();
+/// map.insert("Style".to_string(), Type::Enum("Sales.Color".to_string()));
+///
+/// let identifiers_map = IdentifiersTypeMap::from(map)
+///     .with_enum("Sales.Color", ["Red", "Green", "Blue", "Yellow"]);
+/// ```
+///
+/// Complex types and navigation properties are declared with the types of
+/// their properties, which allows member paths such as `Address/City`.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use odata_params::filters::{IdentifiersTypeMap, Type};
+///
+/// let mut map = HashMap::new();
+/// map.insert("Address".to_string(), Type::Complex("Sales.Address".to_string()));
+///
+/// let identifiers_map = IdentifiersTypeMap::from(map).with_complex_type(
+///     "Sales.Address",
+///     [("City", Type::String), ("Street", Type::String)],
+/// );
+/// ```
+///
+/// Identifiers and properties are not nullable unless their type is marked
+/// with `Type::nullable`, which is required to compare them with `null`.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use odata_params::filters::{IdentifiersTypeMap, Type};
+///
+/// let mut map = HashMap::new();
+/// map.insert("Name".to_string(), Type::String);
+/// map.insert("MiddleName".to_string(), Type::String.nullable());
+///
+/// let identifiers_map: IdentifiersTypeMap = map.into();
+/// ```
+pub struct IdentifiersTypeMap {
+    identifiers: HashMap<String, Type>,
+    enums: HashMap<String, Vec<String>>,
+    complex_types: HashMap<String, HashMap<String, Type>>,
+}
 
 /// Represents a map of functions to their corresponding argument types, optional variadic argument type, and return type.
 ///
@@ -240,11 +745,11 @@ pub struct IdentifiersTypeMap(HashMap<String, Type>);
 ///
 /// let mut map = HashMap::new();
 ///
-/// // Support a `sum` function that takes one `Number` kind as input.
-/// // Returns a `Number`.
+/// // Support a `sum` function that takes one `Decimal` kind as input.
+/// // Returns a `Decimal`.
 /// map.insert(
 ///     "sum".to_string(),
-///     (vec![Type::Number], None, Type::Number)
+///     (vec![Type::Decimal], None, Type::Decimal)
 /// );
 ///
 /// // Support an `any` function that takes at least one `Boolean` kind
@@ -259,16 +764,150 @@ pub struct IdentifiersTypeMap(HashMap<String, Type>);
 ///
 /// let functions_map: FunctionsTypeMap = map.into();
 /// ```
-pub struct FunctionsTypeMap(HashMap<String, (Vec<Type>, Option<Type>, Type)>);
+///
+/// Functions can be overloaded with several signatures, where a call is
+/// valid when any of them accepts its arguments.
+///
+/// ```
+/// use odata_params::filters::{FunctionsTypeMap, Type};
+///
+/// // Support both `round(Decimal)` and `round(Decimal, Int32)`.
+/// let functions_map = FunctionsTypeMap::default()
+///     .with_function("round", [Type::Decimal], None, Type::Decimal)
+///     .with_function("round", [Type::Decimal, Type::Int32], None, Type::Decimal);
+/// ```
+#[derive(Default)]
+pub struct FunctionsTypeMap(HashMap<String, Vec<FunctionSignature>>);
+
+/// The argument types, optional variadic argument type, and return type of
+/// a function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionSignature {
+    /// Types of the required arguments.
+    pub args: Vec<Type>,
+
+    /// Type of any number of extra arguments, if they are allowed.
+    pub variadic: Option<Type>,
+
+    /// Type returned by the function.
+    pub ret: Type,
+}
+
+impl IdentifiersTypeMap {
+    /// Declares an enum type by its qualified name along with its member names.
+    pub fn with_enum<I, S>(mut self, type_name: impl Into<String>, members: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.enums.insert(
+            type_name.into(),
+            members.into_iter().map(Into::into).collect(),
+        );
+        self
+    }
+
+    /// Declares a complex or entity type by its qualified name along with
+    /// the names and types of its properties.
+    pub fn with_complex_type<I, S>(mut self, type_name: impl Into<String>, properties: I) -> Self
+    where
+        I: IntoIterator<Item = (S, Type)>,
+        S: Into<String>,
+    {
+        self.complex_types.insert(
+            type_name.into(),
+            properties
+                .into_iter()
+                .map(|(name, property_type)| (name.into(), property_type))
+                .collect(),
+        );
+        self
+    }
+
+    /// Returns the type of an identifier if it is defined.
+    pub(crate) fn get(&self, name: &str) -> Option<&Type> {
+        self.identifiers.get(name)
+    }
+
+    /// Returns the member names of an enum type if it is declared.
+    pub(crate) fn enum_members(&self, type_name: &str) -> Option<&[String]> {
+        self.enums.get(type_name).map(Vec::as_slice)
+    }
+
+    /// Returns the properties of a complex type if it is declared.
+    pub(crate) fn complex_type(&self, type_name: &str) -> Option<&HashMap<String, Type>> {
+        self.complex_types.get(type_name)
+    }
+
+    /// Returns the type with the given qualified name, which is either a
+    /// primitive `Edm` type or a declared enum or complex type.
+    pub(crate) fn named_type(&self, type_name: &str) -> Option<Type> {
+        let named_type = match type_name {
+            "Edm.Boolean" => Type::Boolean,
+            "Edm.Byte" => Type::Byte,
+            "Edm.SByte" => Type::SByte,
+            "Edm.Int32" => Type::Int32,
+            "Edm.Int64" => Type::Int64,
+            "Edm.Decimal" => Type::Decimal,
+            "Edm.Single" => Type::Single,
+            "Edm.Double" => Type::Double,
+            "Edm.Guid" => Type::Uuid,
+            "Edm.DateTimeOffset" => Type::DateTime,
+            "Edm.Date" => Type::Date,
+            "Edm.TimeOfDay" => Type::Time,
+            "Edm.Duration" => Type::Duration,
+            "Edm.String" => Type::String,
+            "Edm.Binary" => Type::Binary,
+            // Spatial types are named after their shape, such as `Edm.GeographyPoint`.
+            name if name.starts_with("Edm.Geography") => Type::Geography,
+            name if name.starts_with("Edm.Geometry") => Type::Geometry,
+            name if self.enums.contains_key(name) => Type::Enum(name.to_owned()),
+            name if self.complex_types.contains_key(name) => Type::Complex(name.to_owned()),
+            _ => return None,
+        };
+
+        Some(named_type)
+    }
+}
 
 impl From<HashMap<String, Type>> for IdentifiersTypeMap {
     fn from(map: HashMap<String, Type>) -> Self {
-        Self(map)
+        Self {
+            identifiers: map,
+            enums: HashMap::new(),
+            complex_types: HashMap::new(),
+        }
     }
 }
 
 impl From<HashMap<String, (Vec<Type>, Option<Type>, Type)>> for FunctionsTypeMap {
     fn from(map: HashMap<String, (Vec<Type>, Option<Type>, Type)>) -> Self {
-        Self(map)
+        Self(
+            map.into_iter()
+                .map(|(name, signature)| (name, vec![signature.into()]))
+                .collect(),
+        )
+    }
+}
+
+impl From<(Vec<Type>, Option<Type>, Type)> for FunctionSignature {
+    fn from((args, variadic, ret): (Vec<Type>, Option<Type>, Type)) -> Self {
+        Self {
+            args,
+            variadic,
+            ret,
+        }
+    }
+}
+
+impl std::fmt::Display for FunctionSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut args: Vec<String> = self.args.iter().map(|arg| format!("{arg:?}")).collect();
+
+        if let Some(variadic) = &self.variadic {
+            args.push(format!("{variadic:?}..."));
+        }
+
+        write!(f, "({}) -> {:?}", args.join(", "), self.ret)
     }
 }
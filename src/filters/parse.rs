@@ -1,6 +1,12 @@
-use super::{CompareOperator, Expr, ParseError, Value};
+use super::{
+    base64, ArithmeticOperator, CompareOperator, Expr, ExprSpans, LambdaOperator, ParseError,
+    Position, Shape, Span, Spatial, Value,
+};
 use bigdecimal::BigDecimal;
-use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, TimeDelta};
+use peg::str::LineCol;
+use peg::RuleResult;
+use std::cell::RefCell;
 use std::str::FromStr;
 use uuid::Uuid;
 
@@ -14,96 +20,555 @@ use uuid::Uuid;
 /// let filter = "name eq 'John' and isActive eq true";
 /// let result = parse_str(filter).expect("valid filter tree");
 /// ```
+///
+/// Errors point at the location in the query where parsing failed.
+///
+/// ```
+/// use odata_params::filters::{parse_str, ParseError};
+///
+/// let error = parse_str("name eq 'John' an").unwrap_err();
+///
+/// assert!(matches!(error, ParseError::Parsing { offset: 15, line: 1, column: 16, .. }));
+/// assert!(error.to_string().ends_with("name eq 'John' an\n               ^"));
+/// ```
 pub fn parse_str(query: impl AsRef<str>) -> Result<Expr, ParseError> {
-    match odata_filter::parse_str(query.as_ref().trim()) {
+    parse_str_with_spans(query).map(|(expr, _spans)| expr)
+}
+
+/// Parses an OData v4 `$filter` expression string into an `Expr` AST along
+/// with the source locations of each of its nodes.
+///
+/// ```
+/// use odata_params::filters::{parse_str_with_spans, Span};
+///
+/// let filter = "name eq 'John'";
+/// let (expr, spans) = parse_str_with_spans(filter).expect("valid filter tree");
+///
+/// assert_eq!(spans.span, Span { start: 0, end: 14 });
+/// assert_eq!(spans.operator, Some(Span { start: 5, end: 7 }));
+/// assert_eq!(spans.children[1].span, Span { start: 8, end: 14 });
+/// ```
+pub fn parse_str_with_spans(query: impl AsRef<str>) -> Result<(Expr, ExprSpans), ParseError> {
+    let query = query.as_ref();
+
+    match odata_filter::parse_str(query) {
         Ok(expr) => expr,
-        Err(_error) => Err(ParseError::Parsing),
+        Err(error) => Err(syntax_error(query, error)),
+    }
+}
+
+/// Converts a grammar error into a `ParseError` pointing at the failing spot in `source`.
+pub(crate) fn syntax_error(source: &str, error: peg::error::ParseError<LineCol>) -> ParseError {
+    let expected = error.expected.tokens().map(str::to_owned).collect();
+    located_error(source, error.location, expected)
+}
+
+/// Builds a `ParseError` pointing at a spot in `source` where any of the
+/// `expected` tokens could have been parsed.
+fn located_error(source: &str, location: LineCol, mut expected: Vec<String>) -> ParseError {
+    let offset = location.offset;
+    let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[offset..]
+        .find('\n')
+        .map_or(source.len(), |i| offset + i);
+
+    expected.sort();
+    expected.dedup();
+
+    ParseError::Parsing {
+        offset,
+        line: location.line,
+        column: location.column,
+        snippet: source[line_start..line_end]
+            .trim_end_matches('\r')
+            .to_owned(),
+        expected,
+    }
+}
+
+/// A parsed expression along with the source locations of its nodes.
+pub(crate) type Spanned = Result<(Expr, ExprSpans), ParseError>;
+
+/// Holds a part of an option string parsed with another grammar, along with
+/// the offset just past it.
+pub(crate) type Embedded<T> = RefCell<Option<(T, usize)>>;
+
+/// Keeps the furthest error of the parts of an option string parsed with
+/// another grammar from custom rules, such as the expressions of `$orderby`,
+/// since the calling grammar only sees whether such a part matched.
+#[derive(Default)]
+pub(crate) struct EmbeddedErrors(RefCell<Option<(LineCol, Vec<&'static str>)>>);
+
+impl EmbeddedErrors {
+    /// Parses a part with the entry rule of another grammar into the result
+    /// of a custom rule, keeping the error of that grammar if it is the
+    /// furthest.
+    ///
+    /// The entry rule stores its match in the given cell and then fails, so
+    /// that its error still points at the furthest spot it reached when a
+    /// shorter part matched.
+    pub(crate) fn embed<T>(
+        &self,
+        parse: impl FnOnce(&Embedded<T>) -> Result<(), peg::error::ParseError<LineCol>>,
+    ) -> RuleResult<T> {
+        let embedded = Embedded::default();
+
+        if let Err(error) = parse(&embedded) {
+            let mut kept = self.0.borrow_mut();
+            *kept = Some(furthest_error(kept.take(), error));
+        }
+
+        match embedded.into_inner() {
+            Some((value, end)) => RuleResult::Matched(end, value),
+            None => RuleResult::Failed,
+        }
+    }
+
+    /// Parses an expression starting at the offset `start` of `input`.
+    pub(crate) fn expression(&self, input: &str, start: usize) -> RuleResult<Spanned> {
+        self.embed(|embedded| odata_filter::expression(input, start, embedded))
+    }
+
+    /// Converts the error of the calling grammar into a `ParseError`,
+    /// pointing at the furthest spot reached by it or by an embedded part.
+    pub(crate) fn syntax_error(
+        self,
+        source: &str,
+        error: peg::error::ParseError<LineCol>,
+    ) -> ParseError {
+        let (location, expected) = furthest_error(self.0.into_inner(), error);
+
+        located_error(
+            source,
+            location,
+            expected.into_iter().map(str::to_owned).collect(),
+        )
+    }
+}
+
+/// Picks the further of a kept error location and a new error, merging
+/// their expected tokens when both point at the same spot.
+fn furthest_error(
+    kept: Option<(LineCol, Vec<&'static str>)>,
+    error: peg::error::ParseError<LineCol>,
+) -> (LineCol, Vec<&'static str>) {
+    match kept {
+        Some((location, expected)) if location.offset > error.location.offset => {
+            (location, expected)
+        }
+        Some((location, mut expected)) if location.offset == error.location.offset => {
+            expected.extend(error.expected.tokens());
+            (location, expected)
+        }
+        _ => (error.location, error.expected.tokens().collect()),
     }
 }
 
-enum AfterValueExpr {
-    Compare(CompareOperator, Box<Expr>),
-    In(Vec<Expr>),
-    End,
+/// Pairs an expression without children with its source location.
+fn leaf(expr: Expr, start: usize, end: usize) -> (Expr, ExprSpans) {
+    let spans = ExprSpans {
+        span: Span { start, end },
+        operator: None,
+        children: Vec::new(),
+    };
+
+    (expr, spans)
+}
+
+/// Builds a binary expression from two parsed operands and the location of its operator.
+fn build_binary(
+    lhs: Spanned,
+    operator: Span,
+    rhs: Spanned,
+    build: impl FnOnce(Box<Expr>, Box<Expr>) -> Expr,
+) -> Spanned {
+    let (lhs, lhs_spans) = lhs?;
+    let (rhs, rhs_spans) = rhs?;
+
+    let spans = ExprSpans {
+        span: Span {
+            start: lhs_spans.span.start,
+            end: rhs_spans.span.end,
+        },
+        operator: Some(operator),
+        children: vec![lhs_spans, rhs_spans],
+    };
+
+    Ok((build(Box::new(lhs), Box::new(rhs)), spans))
+}
+
+/// Builds a unary expression from its parsed operand and the location of its operator.
+fn build_unary(operator: Span, operand: Spanned, build: impl FnOnce(Box<Expr>) -> Expr) -> Spanned {
+    let (operand, operand_spans) = operand?;
+
+    let spans = ExprSpans {
+        span: Span {
+            start: operator.start,
+            end: operand_spans.span.end,
+        },
+        operator: Some(operator),
+        children: vec![operand_spans],
+    };
+
+    Ok((build(Box::new(operand)), spans))
+}
+
+/// Builds an `in` expression from its parsed operand and list, where `end`
+/// is the location just past the closing parenthesis of the list.
+fn build_in(
+    lhs: Spanned,
+    operator: Span,
+    list: Result<Vec<(Expr, ExprSpans)>, ParseError>,
+    end: usize,
+) -> Spanned {
+    let (lhs, lhs_spans) = lhs?;
+    let (list, list_spans): (Vec<_>, Vec<_>) = list?.into_iter().unzip();
+
+    let spans = ExprSpans {
+        span: Span {
+            start: lhs_spans.span.start,
+            end,
+        },
+        operator: Some(operator),
+        children: std::iter::once(lhs_spans).chain(list_spans).collect(),
+    };
+
+    Ok((Expr::In(Box::new(lhs), list), spans))
+}
+
+/// Builds a lambda expression from its collection, operator and optional
+/// range variable with its parsed predicate, where `end` is the location
+/// just past the closing parenthesis.
+fn build_lambda(
+    collection: (Expr, ExprSpans),
+    op: LambdaOperator,
+    operator: Span,
+    predicate: Option<(String, Spanned)>,
+    end: usize,
+) -> Spanned {
+    let (collection, collection_spans) = collection;
+    let span = Span {
+        start: collection_spans.span.start,
+        end,
+    };
+    let mut children = vec![collection_spans];
+
+    let predicate = match predicate {
+        Some((variable, predicate)) => {
+            let (predicate, predicate_spans) = predicate?;
+            children.push(predicate_spans);
+            Some((variable, Box::new(predicate)))
+        }
+        None => None,
+    };
+
+    let spans = ExprSpans {
+        span,
+        operator: Some(operator),
+        children,
+    };
+
+    Ok((Expr::Lambda(Box::new(collection), op, predicate), spans))
+}
+
+/// Builds a function call from its name and parsed arguments.
+fn build_function(
+    name: String,
+    name_span: Span,
+    args: Result<Vec<(Expr, ExprSpans)>, ParseError>,
+    end: usize,
+) -> Spanned {
+    let (args, args_spans) = args?.into_iter().unzip();
+
+    let spans = ExprSpans {
+        span: Span {
+            start: name_span.start,
+            end,
+        },
+        operator: Some(name_span),
+        children: args_spans,
+    };
+
+    Ok((Expr::Function(name, args), spans))
+}
+
+/// Widens the location of a parsed expression to include its enclosing parentheses.
+fn group(expr: Spanned, start: usize, end: usize) -> Spanned {
+    let (expr, mut spans) = expr?;
+    spans.span = Span { start, end };
+    Ok((expr, spans))
+}
+
+/// Builds an identifier from a single path segment, or a member path otherwise.
+fn path_expr(mut segments: Vec<String>) -> Expr {
+    if segments.len() == 1 {
+        Expr::Identifier(segments.remove(0))
+    } else {
+        Expr::Path(segments)
+    }
+}
+
+/// Combines a UTF-16 surrogate pair given as hexadecimal code units into a character.
+fn surrogate_pair(high: &str, low: &str) -> Option<char> {
+    let high = u32::from_str_radix(high, 16).ok()?;
+    let low = u32::from_str_radix(low, 16).ok()?;
+
+    if (0xD800..0xDC00).contains(&high) && (0xDC00..0xE000).contains(&low) {
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+    } else {
+        None
+    }
+}
+
+/// Builds a duration from its ISO 8601 day, hour, minute and second
+/// components, or `None` if it is out of range.
+fn duration(
+    is_negative: bool,
+    days: Option<&str>,
+    hours: Option<&str>,
+    minutes: Option<&str>,
+    seconds: Option<&str>,
+) -> Option<TimeDelta> {
+    let component = |value: Option<&str>, unit: i64| {
+        TimeDelta::try_seconds(
+            value
+                .unwrap_or("0")
+                .parse::<i64>()
+                .ok()?
+                .checked_mul(unit)?,
+        )
+    };
+
+    let (whole, fraction) = seconds
+        .unwrap_or("0")
+        .split_once('.')
+        .unwrap_or((seconds.unwrap_or("0"), ""));
+    let nanoseconds = format!("{fraction:0<9}").parse::<i64>().ok()?;
+
+    let duration = component(days, 86_400)?
+        .checked_add(&component(hours, 3_600)?)?
+        .checked_add(&component(minutes, 60)?)?
+        .checked_add(&component(Some(whole), 1)?)?
+        .checked_add(&TimeDelta::nanoseconds(nanoseconds))?;
+
+    Some(if is_negative { -duration } else { duration })
 }
 
 peg::parser! {
     /// Parses OData v4 `$filter` expressions.
-    grammar odata_filter() for str {
-        use super::{Expr, CompareOperator, Value, ParseError};
+    pub(crate) grammar odata_filter() for str {
+        use super::{build_binary, build_function, build_in, build_lambda, build_unary, group, leaf, path_expr, surrogate_pair, Spanned};
+        use super::{ArithmeticOperator, Expr, ExprSpans, CompareOperator, LambdaOperator, Value, ParseError, Span};
+        use peg::ParseLiteral;
 
         /// Entry point for parsing a filter expression string.
-        pub(super) rule parse_str() -> Result<Expr, ParseError>
-            = filter()
-
-        /// Parses a filter expression.
-        rule filter() -> Result<Expr, ParseError>
-            = "not" _ e:filter() { Ok(Expr::Not(Box::new(e?))) }
-            / l:any_expr() _ "or" _ r:filter() { Ok(Expr::Or(Box::new(l?), Box::new(r?))) }
-            / l:any_expr() _ "and" _ r:filter() { Ok(Expr::And(Box::new(l?), Box::new(r?))) }
-            / any_expr()
-
-        /// Parses any expression, including grouped expressions and value expressions.
-        rule any_expr() -> Result<Expr, ParseError>
-            = "(" _ e:filter() _ ")" { e }
-            / l:value_expr() _ r:after_value_expr() { Ok(match r? {
-                AfterValueExpr::Compare(op, r) => Expr::Compare(Box::new(l?), op, r),
-                AfterValueExpr::In(r) => Expr::In(Box::new(l?), r),
-                AfterValueExpr::End => l?,
-            }) }
-
-        /// Parses an expression that comes after a value.
-        rule after_value_expr() -> Result<AfterValueExpr, ParseError>
-            = op:comparison_op() _ r:value_expr() { Ok(AfterValueExpr::Compare(op, Box::new(r?))) }
-            / "in" _ "(" _ r:filter_list() _ ")" { Ok(AfterValueExpr::In(r?)) }
-            / { Ok(AfterValueExpr::End) }
-
-        /// Parses a value expression, which can be a function call, a value, or an identifier.
-        rule value_expr() -> Result<Expr, ParseError>
-            = function_call()
-            / v:value() { Ok(Expr::Value(v?)) }
-            / i:identifier() { Ok(Expr::Identifier(i)) }
-
-        /// Parses a comparison operator.
-        rule comparison_op() -> CompareOperator
-            = "eq" { CompareOperator::Equal }
-            / "ne" { CompareOperator::NotEqual }
-            / "gt" { CompareOperator::GreaterThan }
-            / "ge" { CompareOperator::GreaterOrEqual }
-            / "lt" { CompareOperator::LessThan }
-            / "le" { CompareOperator::LessOrEqual }
+        pub(crate) rule parse_str() -> Spanned
+            = _ e:filter() _ { e }
+
+        /// Entry point for parsing the value of a parameter alias, which is
+        /// either a JSON string literal or a filter expression.
+        pub(crate) rule alias_value() -> Result<Expr, ParseError>
+            = _ s:json_string() _ { Ok(Expr::Value(Value::String(s?))) }
+            / _ e:filter() _ { e.map(|(e, _)| e) }
+
+        /// Entry point for parsing an expression that starts at the offset
+        /// `start` within the option string of another grammar, storing it
+        /// in `embedded` along with the offset just past it. The rest of the
+        /// input is left to that grammar.
+        pub(crate) rule expression(start: usize, embedded: &Embedded<Spanned>)
+            = skip_to(start) e:filter() end:position!() quiet!{
+                {? embedded.replace(Some((e, end))); Err::<(), _>("") }
+            }
+
+        /// Moves to the given offset without matching anything.
+        rule skip_to(offset: usize)
+            = #{|_, _| peg::RuleResult::Matched(offset, ())}
+
+        /// Matches a word equal to the given keyword, ignoring its case.
+        rule keyword(word: &'static str)
+            = quiet!{
+                w:$(['a'..='z'|'A'..='Z']+) {?
+                    if w.eq_ignore_ascii_case(word) { Ok(()) } else { Err(word) }
+                }
+            }
+            / expected!(word)
+
+        /// Matches a single character that may continue an identifier.
+        rule identifier_character()
+            = ['a'..='z'|'A'..='Z'|'_'|'0'..='9']
+
+        /// Parses a filter expression where operators bind, from loosest
+        /// to tightest: `or`, `and`, equality, relational, additive,
+        /// multiplicative, unary `not` and `-`, then `has` and `in`. Binary operators
+        /// are left-associative. A minus sign directly followed by a digit is
+        /// parsed as a negative number instead.
+        rule filter() -> Spanned = precedence!{
+            l:(@) _ o:operator("or") _ r:@ { build_binary(l, o, r, Expr::Or) }
+            --
+            l:(@) _ o:operator("and") _ r:@ { build_binary(l, o, r, Expr::And) }
+            --
+            l:(@) _ s:position!() op:equality_op() e:position!() _ r:@ {
+                build_binary(l, Span { start: s, end: e }, r, |l, r| Expr::Compare(l, op, r))
+            }
+            --
+            l:(@) _ s:position!() op:relational_op() e:position!() _ r:@ {
+                build_binary(l, Span { start: s, end: e }, r, |l, r| Expr::Compare(l, op, r))
+            }
+            --
+            l:(@) _ o:operator("add") _ r:@ { build_binary(l, o, r, |l, r| Expr::Arithmetic(l, ArithmeticOperator::Add, r)) }
+            l:(@) _ o:operator("sub") _ r:@ { build_binary(l, o, r, |l, r| Expr::Arithmetic(l, ArithmeticOperator::Sub, r)) }
+            --
+            l:(@) _ o:operator("mul") _ r:@ { build_binary(l, o, r, |l, r| Expr::Arithmetic(l, ArithmeticOperator::Mul, r)) }
+            l:(@) _ o:operator("div") _ r:@ { build_binary(l, o, r, |l, r| Expr::Arithmetic(l, ArithmeticOperator::Div, r)) }
+            l:(@) _ o:operator("divby") _ r:@ { build_binary(l, o, r, |l, r| Expr::Arithmetic(l, ArithmeticOperator::DivBy, r)) }
+            l:(@) _ o:operator("mod") _ r:@ { build_binary(l, o, r, |l, r| Expr::Arithmetic(l, ArithmeticOperator::Mod, r)) }
+            --
+            o:operator("not") _ e:@ { build_unary(o, e, Expr::Not) }
+            s:position!() "-" !(['0'..='9'] / ("INF" / duration()) !identifier_character()) _ e:@ { build_unary(Span { start: s, end: s + 1 }, e, Expr::Negate) }
+            --
+            l:(@) _ o:operator("has") _ r:@ { build_binary(l, o, r, Expr::Has) }
+            l:(@) _ o:operator("in") _ "(" _ r:filter_list() _ ")" e:position!() { build_in(l, o, r, e) }
+            l:(@) _ o:operator("in") _ r:collection_items() e:position!() { build_in(l, o, r, e) }
+            l:(@) _ o:operator("in") _ r:@ { build_binary(l, o, r, Expr::InCollection) }
+            --
+            s:position!() "(" _ e:filter() _ ")" end:position!() { group(e, s, end) }
+            e:value_expr() { e }
+        }
+
+        /// Parses an operator keyword and returns its location.
+        rule operator(w: &'static str) -> Span
+            = s:position!() word(w) e:position!() { Span { start: s, end: e } }
+
+        /// Matches a keyword that is not immediately followed by more identifier characters.
+        rule word(w: &'static str)
+            = quiet!{ ##parse_string_literal(w) !identifier_character() } / expected!(w)
+
+        /// Parses a value expression, which can be a lambda, a type function,
+        /// a function call, a parameter alias, a value, an identifier, or a
+        /// member path.
+        rule value_expr() -> Spanned
+            = lambda()
+            / type_function()
+            / function_call()
+            / s:position!() "@" a:identifier() e:position!() { Ok(leaf(Expr::Alias(a), s, e)) }
+            / s:position!() v:value() e:position!() { Ok(leaf(Expr::Value(v?), s, e)) }
+            / s:position!() p:path() e:position!() { Ok(leaf(path_expr(p), s, e)) }
+
+        /// Parses an equality operator.
+        rule equality_op() -> CompareOperator
+            = word("eq") { CompareOperator::Equal }
+            / word("ne") { CompareOperator::NotEqual }
+
+        /// Parses a relational operator.
+        rule relational_op() -> CompareOperator
+            = word("gt") { CompareOperator::GreaterThan }
+            / word("ge") { CompareOperator::GreaterOrEqual }
+            / word("lt") { CompareOperator::LessThan }
+            / word("le") { CompareOperator::LessOrEqual }
+
+        /// Parses a lambda operator applied to a collection, such as `Tags/any(t: t eq 'urgent')`.
+        rule lambda() -> Spanned
+            = s:position!() c:path() m:position!() "/" o:operator("any") _ "(" _ ")" e:position!() {
+                build_lambda(leaf(path_expr(c), s, m), LambdaOperator::Any, o, None, e)
+            }
+            / s:position!() c:path() m:position!() "/" os:position!() op:lambda_op() oe:position!() _ "(" _ v:identifier() _ ":" _ p:filter() _ ")" e:position!() {
+                build_lambda(leaf(path_expr(c), s, m), op, Span { start: os, end: oe }, Some((v, p)), e)
+            }
+
+        /// Parses a lambda operator.
+        rule lambda_op() -> LambdaOperator
+            = word("any") { LambdaOperator::Any }
+            / word("all") { LambdaOperator::All }
+
+        /// Parses a call to the type function `cast` or `isof`, with an optional
+        /// operand followed by a qualified type name, such as `cast(Price, Edm.String)`.
+        rule type_function() -> Spanned
+            = s:position!() f:$(quiet!{ word("cast") / word("isof") }) m:position!() _ "(" _ a:(a:filter() _ "," _ { a })? ts:position!() t:type_name() te:position!() _ ")" e:position!() {
+                let type_name = Ok(leaf(Expr::TypeName(t), ts, te));
+                build_function(f.to_string(), Span { start: s, end: m }, a.into_iter().chain([type_name]).collect(), e)
+            }
+
+        /// Parses a qualified type name, such as `Edm.Int32` or `Sales.Address`.
+        rule type_name() -> String
+            = quiet!{ t:$(identifier() ("." identifier())+) { t.to_string() } }
+            / expected!("type name")
 
         /// Parses a function call with a name and arguments.
-        rule function_call() -> Result<Expr, ParseError>
-            = f:identifier() _ "(" _ l:filter_list() _ ")" { Ok(Expr::Function(f, l?)) }
+        rule function_call() -> Spanned
+            = s:position!() f:$(identifier() ++ ".") m:position!() _ "(" _ l:filter_list() _ ")" e:position!() {
+                build_function(f.to_string(), Span { start: s, end: m }, l, e)
+            }
+
+        /// Parses the segments of a member path separated by slashes, stopping
+        /// before a lambda operator applied to the path.
+        rule path() -> Vec<String>
+            = (!(lambda_op() _ "(") i:identifier() { i }) ++ "/"
 
         /// Parses an identifier.
         rule identifier() -> String
-            = s:$(['a'..='z'|'A'..='Z'|'_']['a'..='z'|'A'..='Z'|'_'|'0'..='9']+) { s.to_string() }
+            = quiet!{ s:$(['a'..='z'|'A'..='Z'|'_']['a'..='z'|'A'..='Z'|'_'|'0'..='9']*) { s.to_string() } }
+            / expected!("identifier")
 
-        /// Parses a value, which can be a string, datetime, date, time, number, boolean, or null.
+        /// Parses a value, which can be a string, binary, spatial, duration, datetime, date, time, number, boolean, enum, collection, complex, or null.
         rule value() -> Result<Value, ParseError>
-            = string_value()
-            / datetime_value()
-            / date_value()
-            / time_value()
-            / uuid_value()
-            / number_value()
-            / v:bool_value() { Ok(v) }
-            / v:null_value() { Ok(v) }
+            = quiet!{
+                string_value()
+                / collection_value()
+                / complex_value()
+                / binary_value()
+                / spatial_value()
+                / v:enum_value() { Ok(v) }
+                / duration_value()
+                / datetime_value()
+                / date_value()
+                / time_value()
+                / uuid_value()
+                / number_value()
+                / v:special_number_value() { Ok(v) }
+                / v:bool_value() { Ok(v) }
+                / v:null_value() { Ok(v) }
+            }
+            / expected!("literal")
+
+        /// Parses an enum value with a qualified type name, such as `Sales.Color'Red,Blue'`.
+        rule enum_value() -> Value
+            = t:$(identifier() ("." identifier())+) "'" m:identifier() ++ ( _ "," _ ) "'" {
+                Value::Enum { type_name: t.to_string(), members: m }
+            }
 
         /// Parses a boolean value.
         rule bool_value() -> Value
             = ['t'|'T']['r'|'R']['u'|'U']['e'|'E'] { Value::Bool(true) }
             / ['f'|'F']['a'|'A']['l'|'L']['s'|'S']['e'|'E'] { Value::Bool(false) }
 
-        /// Parses a numeric value.
+        /// Parses a numeric value, where an exponent or a suffix gives its type.
         rule number_value() -> Result<Value, ParseError>
-            = n:$(['0'..='9']+ ("." ['0'..='9']*)?) { Ok(Value::Number(BigDecimal::from_str(n).map_err(|_| ParseError::ParsingNumber)?)) }
+            = s:position!() n:$("-"? ['0'..='9']+ ("." ['0'..='9']*)?) x:$(['e'|'E'] ['+'|'-']? ['0'..='9']+)?
+              suffix:$(['m'|'M'|'l'|'L'|'d'|'D'|'f'|'F'])? !identifier_character() e:position!() {
+                let error = || ParseError::ParsingNumber { span: Span { start: s, end: e } };
+                let number = || BigDecimal::from_str(&format!("{n}{}", x.unwrap_or_default())).map_err(|_| error());
+
+                match (suffix.map(str::to_ascii_lowercase).as_deref(), x) {
+                    (None, None) => Ok(Value::Number(number()?)),
+                    (None | Some("d"), _) => Ok(Value::Double(number()?)),
+                    (Some("f"), _) => Ok(Value::Single(number()?)),
+                    (Some("m"), _) => Ok(Value::Decimal(number()?)),
+                    (Some(_), None) if !n.contains('.') => Ok(Value::Int64(n.parse().map_err(|_| error())?)),
+                    _ => Err(error()),
+                }
+            }
+
+        /// Parses the special floating point values.
+        rule special_number_value() -> Value
+            = "-INF" !identifier_character() { Value::NegativeInfinity }
+            / "INF" !identifier_character() { Value::Infinity }
+            / "NaN" !identifier_character() { Value::NaN }
 
         /// Parses a uuid value.
         rule uuid_value() -> Result<Value, ParseError>
-            = id:$(hex()*<8> "-" hex()*<4> "-" hex()*<4> "-" hex()*<4> "-" hex()*<12> ) { Ok(Value::Uuid(Uuid::parse_str(id).map_err(|_| ParseError::ParsingUuid)?)) }
+            = s:position!() id:$(hex()*<8> "-" hex()*<4> "-" hex()*<4> "-" hex()*<4> "-" hex()*<12> ) e:position!() {
+                Ok(Value::Uuid(Uuid::parse_str(id).map_err(|_| ParseError::ParsingUuid { span: Span { start: s, end: e } })?))
+            }
 
         /// Parses a single hexadecimal digit.
         rule hex() -> char
@@ -111,12 +576,12 @@ peg::parser! {
 
         /// Parses a time value in the format `HH:MM:SS` or `HH:MM`.
         rule time() -> Result<NaiveTime, ParseError>
-            = hm:$($(['0'..='9']*<1,2>) ":" $(['0'..='9']*<2>)) s:$(":" $(['0'..='9']*<2>))? ms:$("." $(['0'..='9']*<1,9>))? {
+            = start:position!() hm:$($(['0'..='9']*<1,2>) ":" $(['0'..='9']*<2>)) s:$(":" $(['0'..='9']*<2>))? ms:$("." $(['0'..='9']*<1,9>))? end:position!() {
                 match (s, ms) {
                     (Some(s), Some(ms)) => NaiveTime::parse_from_str(&format!("{hm}{s}{ms}"), "%H:%M:%S%.f"),
                     (Some(s), None) => NaiveTime::parse_from_str(&format!("{hm}{s}"), "%H:%M:%S"),
                     (None, _) => NaiveTime::parse_from_str(hm, "%H:%M"),
-                }.map_err(|_| ParseError::ParsingTime)
+                }.map_err(|_| ParseError::ParsingTime { span: Span { start, end } })
             }
 
         /// Parses a time value.
@@ -125,7 +590,9 @@ peg::parser! {
 
         /// Parses a date value in the format `YYYY-MM-DD`.
         rule date() -> Result<NaiveDate, ParseError>
-            = d:$($(['0'..='9']*<4>) "-" $(['0'..='9']*<2>) "-" $(['0'..='9']*<2>)) { NaiveDate::parse_from_str(d, "%Y-%m-%d").map_err(|_| ParseError::ParsingDate) }
+            = s:position!() d:$($(['0'..='9']*<4>) "-" $(['0'..='9']*<2>) "-" $(['0'..='9']*<2>)) e:position!() {
+                NaiveDate::parse_from_str(d, "%Y-%m-%d").map_err(|_| ParseError::ParsingDate { span: Span { start: s, end: e } })
+            }
 
         /// Parses a date value.
         rule date_value() -> Result<Value, ParseError>
@@ -133,22 +600,142 @@ peg::parser! {
 
         /// Parses a named timezone.
         rule timezone_name() -> Result<chrono_tz::Tz, ParseError>
-            = z:$(['a'..='z'|'A'..='Z'|'-'|'_'|'/'|'+']['a'..='z'|'A'..='Z'|'-'|'_'|'/'|'+'|'0'..='9']+) { z.parse::<chrono_tz::Tz>().map_err(|_| ParseError::ParsingTimeZoneNamed) }
+            = s:position!() z:$(['a'..='z'|'A'..='Z'|'-'|'_'|'/'|'+']['a'..='z'|'A'..='Z'|'-'|'_'|'/'|'+'|'0'..='9']+) e:position!() {
+                z.parse::<chrono_tz::Tz>().map_err(|_| ParseError::ParsingTimeZoneNamed { span: Span { start: s, end: e } })
+            }
 
         /// Parses a timezone offset.
         rule timezone_offset() -> Result<FixedOffset, ParseError>
-            = "Z" { "+0000".parse().map_err(|_| ParseError::ParsingTimeZone) }
-            / z:$($(['-'|'+']) $(['0'..='9']*<2>) ":"? $(['0'..='9']*<2>)) { z.parse().map_err(|_| ParseError::ParsingTimeZone) }
-            / z:$($(['-'|'+']) $(['0'..='9']*<2>)) { format!("{z}00").parse().map_err(|_| ParseError::ParsingTimeZone) }
+            = "Z" { Ok(FixedOffset::east_opt(0).expect("zero offset")) }
+            / s:position!() z:$($(['-'|'+']) $(['0'..='9']*<2>) ":"? $(['0'..='9']*<2>)) e:position!() {
+                z.parse().map_err(|_| ParseError::ParsingTimeZone { span: Span { start: s, end: e } })
+            }
+            / s:position!() z:$($(['-'|'+']) $(['0'..='9']*<2>)) e:position!() {
+                format!("{z}00").parse().map_err(|_| ParseError::ParsingTimeZone { span: Span { start: s, end: e } })
+            }
 
         /// Parses a datetime value in the format `YYYY-MM-DDTHH:MM:SSZ` or `YYYY-MM-DDTHH:MM:SS+01:00`.
-        rule datetime() -> Result<DateTime<Utc>, ParseError>
-            = d:date() "T" t:time() z:timezone_offset() { Ok(d?.and_time(t?).and_local_timezone(z?).earliest().ok_or(ParseError::ParsingDateTime)?.to_utc()) }
-            / d:date() "T" t:time() z:timezone_name() { Ok(d?.and_time(t?).and_local_timezone(z?).earliest().ok_or(ParseError::ParsingDateTime)?.to_utc()) }
+        rule datetime() -> Result<(DateTime<FixedOffset>, Option<chrono_tz::Tz>), ParseError>
+            = s:position!() d:date() "T" t:time() z:timezone_offset() e:position!() {
+                Ok((d?.and_time(t?).and_local_timezone(z?).earliest().ok_or(ParseError::ParsingDateTime { span: Span { start: s, end: e } })?, None))
+            }
+            / s:position!() d:date() "T" t:time() z:timezone_name() e:position!() {
+                let z = z?;
+                Ok((d?.and_time(t?).and_local_timezone(z).earliest().ok_or(ParseError::ParsingDateTime { span: Span { start: s, end: e } })?.fixed_offset(), Some(z)))
+            }
 
         /// Parses a datetime value.
         rule datetime_value() -> Result<Value, ParseError>
-            = dt:datetime() { Ok(Value::DateTime(dt?)) }
+            = dt:datetime() { let (dt, zone) = dt?; Ok(Value::DateTime(dt, zone)) }
+
+        /// Parses an ISO 8601 duration of days, hours, minutes and seconds, such as `P1DT2H30M`.
+        rule duration() -> Result<TimeDelta, ParseError>
+            = s:position!() n:"-"? "P" d:(d:$(['0'..='9']+) "D" { d })?
+              t:("T" h:(h:$(['0'..='9']+) "H" { h })? m:(m:$(['0'..='9']+) "M" { m })? sec:(sec:$(['0'..='9']+ ("." ['0'..='9']*<1,9>)?) "S" { sec })? {?
+                  if h.is_some() || m.is_some() || sec.is_some() { Ok((h, m, sec)) } else { Err("duration time") }
+              })?
+              e:position!() {?
+                match (d, t) {
+                    (None, None) => Err("duration"),
+                    (d, t) => {
+                        let (h, m, sec) = t.unwrap_or_default();
+                        Ok(duration(n.is_some(), d, h, m, sec).ok_or(ParseError::ParsingDuration { span: Span { start: s, end: e } }))
+                    }
+                }
+            }
+
+        /// Parses a duration value, either as `duration'P1DT2H'` or as a bare `P1DT2H`.
+        ///
+        /// A bare duration is only read when no identifier character follows
+        /// it, but it is tried before identifiers, so a property named like a
+        /// duration, such as `P1D`, is read as a duration instead.
+        rule duration_value() -> Result<Value, ParseError>
+            = "duration'" d:duration() "'" { Ok(Value::Duration(d?)) }
+            / d:duration() !identifier_character() { Ok(Value::Duration(d?)) }
+
+        /// Parses a base64url binary value, such as `binary'T0RhdGE='`.
+        rule binary_value() -> Result<Value, ParseError>
+            = "binary'" s:position!() b:$([^'\'']*) e:position!() "'" {
+                Ok(Value::Binary(base64::decode(b).ok_or(ParseError::ParsingBinary { span: Span { start: s, end: e } })?))
+            }
+
+        /// Parses a geography or geometry value written as well-known text, such as
+        /// `geography'SRID=4326;POINT(-73.5 45.5)'`.
+        rule spatial_value() -> Result<Value, ParseError>
+            = "geography'" g:spatial() "'" { Ok(Value::Geography(g?)) }
+            / "geometry'" g:spatial() "'" { Ok(Value::Geometry(g?)) }
+
+        /// Parses a shape with an optional spatial reference system identifier.
+        rule spatial() -> Result<Spatial, ParseError>
+            = srid:(keyword("SRID") "=" n:$(['0'..='9']*<1,9>) ";" {? n.parse().or(Err("SRID")) })? shape:shape() {
+                Ok(Spatial { srid, shape: shape? })
+            }
+
+        /// Parses the well-known text of a shape.
+        rule shape() -> Result<Shape, ParseError>
+            = keyword("POINT") _ "(" _ p:position() _ ")" { Ok(Shape::Point(p)) }
+            / keyword("LINESTRING") _ l:line_string() { Ok(Shape::LineString(l?)) }
+            / keyword("POLYGON") _ p:polygon() { Ok(Shape::Polygon(p?)) }
+            / keyword("MULTIPOINT") _ "(" _ p:("(" _ p:position() _ ")" { p }) ** (_ "," _) _ ")" {
+                Ok(Shape::MultiPoint(p))
+            }
+            / keyword("MULTILINESTRING") _ "(" _ l:line_string() ** (_ "," _) _ ")" {
+                Ok(Shape::MultiLineString(l.into_iter().collect::<Result<_, _>>()?))
+            }
+            / keyword("MULTIPOLYGON") _ "(" _ p:polygon() ** (_ "," _) _ ")" {
+                Ok(Shape::MultiPolygon(p.into_iter().collect::<Result<_, _>>()?))
+            }
+            / (keyword("COLLECTION") / keyword("GEOMETRYCOLLECTION")) _ "(" _ s:shape() ** (_ "," _) _ ")" {
+                Ok(Shape::Collection(s.into_iter().collect::<Result<_, _>>()?))
+            }
+
+        /// Parses two or more positions of a line string.
+        rule line_string() -> Result<Vec<Position>, ParseError>
+            = s:position!() "(" _ p:position() ++ (_ "," _) _ ")" e:position!() {
+                if p.len() >= 2 { Ok(p) } else { Err(ParseError::ParsingSpatial { span: Span { start: s, end: e } }) }
+            }
+
+        /// Parses the rings of a polygon, each closed by ending on its first position.
+        rule polygon() -> Result<Vec<Vec<Position>>, ParseError>
+            = "(" _ r:ring() ++ (_ "," _) _ ")" { r.into_iter().collect() }
+
+        /// Parses a closed ring of at least four positions.
+        rule ring() -> Result<Vec<Position>, ParseError>
+            = s:position!() "(" _ p:position() ++ (_ "," _) _ ")" e:position!() {
+                if p.len() >= 4 && p.first() == p.last() { Ok(p) } else { Err(ParseError::ParsingSpatial { span: Span { start: s, end: e } }) }
+            }
+
+        /// Parses a position of two coordinates separated by spaces.
+        rule position() -> Position
+            = x:coordinate() " "+ y:coordinate() { Position { x, y } }
+
+        /// Parses a single coordinate of a position.
+        rule coordinate() -> BigDecimal
+            = n:$("-"? ['0'..='9']+ ("." ['0'..='9']+)? (['e'|'E'] ['+'|'-']? ['0'..='9']+)?) {?
+                BigDecimal::from_str(n).or(Err("coordinate"))
+            }
+
+        /// Parses a collection value written as a JSON array, such as `[1,2,3]` or `['a','b']`.
+        rule collection_value() -> Result<Value, ParseError>
+            = "[" _ l:json_value() ** (_ "," _) _ "]" { Ok(Value::Collection(l.into_iter().collect::<Result<_, _>>()?)) }
+
+        /// Parses the elements of a collection value as a list of expressions
+        /// with their spans, for the right hand side of an `in` expression.
+        rule collection_items() -> Result<Vec<(Expr, ExprSpans)>, ParseError>
+            = "[" _ l:(s:position!() v:json_value() e:position!() { Ok(leaf(Expr::Value(v?), s, e)) }) ** (_ "," _) _ "]" {
+                l.into_iter().collect()
+            }
+
+        /// Parses a complex value written as a JSON object, such as `{"City":"Montreal"}`.
+        rule complex_value() -> Result<Value, ParseError>
+            = "{" _ m:(k:json_string() _ ":" _ v:json_value() { Ok((k?, v?)) }) ** (_ "," _) _ "}" {
+                Ok(Value::Complex(m.into_iter().collect::<Result<_, _>>()?))
+            }
+
+        /// Parses a member of a collection or complex value, which can be a JSON string or any value.
+        rule json_value() -> Result<Value, ParseError>
+            = s:json_string() { Ok(Value::String(s?)) }
+            / value()
 
         /// Parses a string value enclosed in single quotes.
         rule string_value() -> Result<Value, ParseError>
@@ -164,24 +751,44 @@ peg::parser! {
             / "r" { Ok('\r') }
             / "t" { Ok('\t') }
             / r"\" { Ok('\\') }
-            / "u" sequence:$(hex()*<1,8>) {
-                u32::from_str_radix(sequence, 16).ok().and_then(char::from_u32).ok_or(ParseError::ParsingUnicodeCodePoint)
+            / s:position!() "u" sequence:$(hex()*<1,8>) e:position!() {
+                u32::from_str_radix(sequence, 16).ok().and_then(char::from_u32).ok_or(ParseError::ParsingUnicodeCodePoint { span: Span { start: s - 1, end: e } })
+            }
+
+        /// Parses a JSON string enclosed in double quotes.
+        rule json_string() -> Result<String, ParseError>
+            = "\"" s:json_string_content()* "\"" { s.into_iter().collect() }
+
+        rule json_string_content() -> Result<char, ParseError>
+            = r"\" e:json_escape_character() { e }
+            / c:[^'"'|'\\'] { Ok(c) }
+
+        rule json_escape_character() -> Result<char, ParseError>
+            = "\"" { Ok('"') }
+            / r"\" { Ok('\\') }
+            / "/" { Ok('/') }
+            / "b" { Ok('\u{8}') }
+            / "f" { Ok('\u{c}') }
+            / "n" { Ok('\n') }
+            / "r" { Ok('\r') }
+            / "t" { Ok('\t') }
+            / "u" high:$(hex()*<4>) r"\u" low:$(hex()*<4>) {?
+                surrogate_pair(high, low).map(Ok).ok_or("surrogate pair")
+            }
+            / s:position!() "u" sequence:$(hex()*<4>) e:position!() {
+                u32::from_str_radix(sequence, 16).ok().and_then(char::from_u32).ok_or(ParseError::ParsingUnicodeCodePoint { span: Span { start: s - 1, end: e } })
             }
 
         /// Parses a null value.
         rule null_value() -> Value
             = ['n'|'N']['u'|'U']['l'|'L']['l'|'L'] { Value::Null }
 
-        /// Parses a list of value expressions separated by commas.
-        rule value_list() -> Result<Vec<Expr>, ParseError>
-            = v:value_expr() ** ( _ "," _ ) { v.into_iter().collect() }
-
         /// Parses a list of filter expressions separated by commas.
-        rule filter_list() -> Result<Vec<Expr>, ParseError>
+        rule filter_list() -> Result<Vec<(Expr, ExprSpans)>, ParseError>
             = v:filter() ** ( _ "," _ ) { v.into_iter().collect() }
 
         /// Matches zero or more whitespace characters.
         rule _()
-            = [' '|'\t'|'\n'|'\r']*
+            = quiet!{ [' '|'\t'|'\n'|'\r']* }
     }
 }
@@ -0,0 +1,126 @@
+use super::parse::{odata_filter, syntax_error};
+use super::{AliasError, Expr, Value};
+use std::collections::HashMap;
+
+/// Replaces every parameter alias in an `Expr` AST with its parsed value.
+///
+/// The `aliases` map is keyed by alias names without their `@` prefix.
+/// Alias values are parsed as filter expressions, or as JSON string
+/// literals when enclosed in double quotes, and may refer to other aliases.
+/// An alias given as the collection of an `in` operator, such as in
+/// `Name in @names`, is expanded into a list of the elements of its
+/// collection value.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use odata_params::filters::{parse_str, resolve_aliases};
+///
+/// let mut aliases = HashMap::new();
+/// aliases.insert("minPrice".to_string(), "10".to_string());
+///
+/// let expr = parse_str("Price gt @minPrice").expect("valid filter tree");
+/// let resolved = resolve_aliases(&expr, &aliases).expect("defined aliases");
+///
+/// assert_eq!(resolved, parse_str("Price gt 10").expect("valid filter tree"));
+/// ```
+pub fn resolve_aliases(expr: &Expr, aliases: &HashMap<String, String>) -> Result<Expr, AliasError> {
+    Resolver {
+        aliases,
+        resolved: HashMap::new(),
+        resolving: Vec::new(),
+    }
+    .resolve(expr)
+}
+
+/// Tracks alias values that have been resolved and the aliases currently
+/// being resolved, which is used to detect cycles.
+struct Resolver<'a> {
+    aliases: &'a HashMap<String, String>,
+    resolved: HashMap<String, Expr>,
+    resolving: Vec<String>,
+}
+
+impl Resolver<'_> {
+    fn resolve(&mut self, expr: &Expr) -> Result<Expr, AliasError> {
+        let boxed = |resolver: &mut Self, expr: &Expr| resolver.resolve(expr).map(Box::new);
+
+        Ok(match expr {
+            Expr::Or(lhs, rhs) => Expr::Or(boxed(self, lhs)?, boxed(self, rhs)?),
+            Expr::And(lhs, rhs) => Expr::And(boxed(self, lhs)?, boxed(self, rhs)?),
+            Expr::Not(inner) => Expr::Not(boxed(self, inner)?),
+            Expr::Compare(lhs, op, rhs) => Expr::Compare(boxed(self, lhs)?, *op, boxed(self, rhs)?),
+            Expr::Arithmetic(lhs, op, rhs) => {
+                Expr::Arithmetic(boxed(self, lhs)?, *op, boxed(self, rhs)?)
+            }
+            Expr::Negate(inner) => Expr::Negate(boxed(self, inner)?),
+            Expr::Has(lhs, rhs) => Expr::Has(boxed(self, lhs)?, boxed(self, rhs)?),
+            Expr::In(lhs, values) => Expr::In(boxed(self, lhs)?, self.resolve_all(values)?),
+            Expr::InCollection(lhs, collection) => {
+                let lhs = boxed(self, lhs)?;
+
+                match self.resolve(collection)? {
+                    Expr::Value(Value::Collection(values)) => {
+                        Expr::In(lhs, values.into_iter().map(Expr::Value).collect())
+                    }
+                    collection => Expr::InCollection(lhs, Box::new(collection)),
+                }
+            }
+            Expr::Lambda(collection, op, predicate) => Expr::Lambda(
+                boxed(self, collection)?,
+                *op,
+                match predicate {
+                    Some((variable, predicate)) => {
+                        Some((variable.to_owned(), boxed(self, predicate)?))
+                    }
+                    None => None,
+                },
+            ),
+            Expr::Function(name, args) => Expr::Function(name.to_owned(), self.resolve_all(args)?),
+            Expr::Alias(name) => self.resolve_alias(name)?,
+            Expr::Identifier(_) | Expr::Path(_) | Expr::TypeName(_) | Expr::Value(_) => {
+                expr.clone()
+            }
+        })
+    }
+
+    fn resolve_all(&mut self, exprs: &[Expr]) -> Result<Vec<Expr>, AliasError> {
+        exprs.iter().map(|expr| self.resolve(expr)).collect()
+    }
+
+    fn resolve_alias(&mut self, name: &str) -> Result<Expr, AliasError> {
+        if let Some(expr) = self.resolved.get(name) {
+            return Ok(expr.clone());
+        }
+
+        if self.resolving.iter().any(|resolving| resolving == name) {
+            return Err(AliasError::CyclicAlias {
+                name: name.to_owned(),
+            });
+        }
+
+        let value = self
+            .aliases
+            .get(name)
+            .ok_or_else(|| AliasError::UndefinedAlias {
+                name: name.to_owned(),
+            })?;
+
+        let invalid = |error| AliasError::InvalidAliasValue {
+            name: name.to_owned(),
+            error,
+        };
+
+        let parsed = match odata_filter::alias_value(value) {
+            Ok(expr) => expr.map_err(invalid)?,
+            Err(error) => return Err(invalid(syntax_error(value, error))),
+        };
+
+        self.resolving.push(name.to_owned());
+        let expr = self.resolve(&parsed);
+        self.resolving.pop();
+
+        let expr = expr?;
+        self.resolved.insert(name.to_owned(), expr.clone());
+        Ok(expr)
+    }
+}
@@ -0,0 +1,195 @@
+use super::{FunctionSignature, FunctionsTypeMap, Type};
+
+impl FunctionsTypeMap {
+    /// Returns the canonical functions defined by the OData v4 standard.
+    ///
+    /// String, collection, date and time, arithmetic, and geo functions are
+    /// declared with their standard names and the signatures of all their
+    /// overloads. Collection functions, including the collection forms of
+    /// the string functions, are declared for collections of every primitive
+    /// type. The type functions `cast` and `isof` take a type name instead of
+    /// a value, and are validated without being declared.
+    ///
+    /// Custom functions can be added to the catalog with `with_function`,
+    /// or a whole map can be combined with it using `merge`.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use odata_params::filters::{parse_str, FunctionsTypeMap, IdentifiersTypeMap, Type};
+    ///
+    /// let identifiers = IdentifiersTypeMap::from(HashMap::from([
+    ///     ("Name".to_string(), Type::String),
+    ///     ("CreatedAt".to_string(), Type::DateTime),
+    /// ]));
+    /// let functions = FunctionsTypeMap::odata_standard()
+    ///     .with_function("has_role", [Type::String], None, Type::Boolean);
+    ///
+    /// let expr = parse_str("contains(tolower(Name), 'john') and year(CreatedAt) ge 2020 and has_role('admin')")
+    ///     .expect("valid filter tree");
+    ///
+    /// assert_eq!(expr.validate(&identifiers, &functions), Ok(Type::Boolean));
+    /// ```
+    pub fn odata_standard() -> Self {
+        use Type::*;
+
+        let functions: Vec<(&str, Vec<Type>, Option<Type>, Type)> = vec![
+            // String functions.
+            ("concat", vec![String, String], None, String),
+            ("contains", vec![String, String], None, Boolean),
+            ("endswith", vec![String, String], None, Boolean),
+            ("indexof", vec![String, String], None, Int32),
+            ("length", vec![String], None, Int32),
+            ("matchesPattern", vec![String, String], None, Boolean),
+            ("startswith", vec![String, String], None, Boolean),
+            ("substring", vec![String, Int32], None, String),
+            ("substring", vec![String, Int32, Int32], None, String),
+            ("tolower", vec![String], None, String),
+            ("toupper", vec![String], None, String),
+            ("trim", vec![String], None, String),
+            // Date and time functions.
+            ("date", vec![DateTime], None, Date),
+            ("day", vec![DateTime], None, Int32),
+            ("day", vec![Date], None, Int32),
+            ("fractionalseconds", vec![DateTime], None, Decimal),
+            ("fractionalseconds", vec![Time], None, Decimal),
+            ("hour", vec![DateTime], None, Int32),
+            ("hour", vec![Time], None, Int32),
+            ("maxdatetime", vec![], None, DateTime),
+            ("mindatetime", vec![], None, DateTime),
+            ("minute", vec![DateTime], None, Int32),
+            ("minute", vec![Time], None, Int32),
+            ("month", vec![DateTime], None, Int32),
+            ("month", vec![Date], None, Int32),
+            ("now", vec![], None, DateTime),
+            ("second", vec![DateTime], None, Int32),
+            ("second", vec![Time], None, Int32),
+            ("time", vec![DateTime], None, Time),
+            ("totaloffsetminutes", vec![DateTime], None, Int32),
+            ("totalseconds", vec![Duration], None, Decimal),
+            ("year", vec![DateTime], None, Int32),
+            ("year", vec![Date], None, Int32),
+            // Arithmetic functions.
+            ("ceiling", vec![Decimal], None, Decimal),
+            ("ceiling", vec![Double], None, Double),
+            ("floor", vec![Decimal], None, Decimal),
+            ("floor", vec![Double], None, Double),
+            ("round", vec![Decimal], None, Decimal),
+            ("round", vec![Double], None, Double),
+            // Geo functions.
+            ("geo.distance", vec![Geography, Geography], None, Double),
+            ("geo.distance", vec![Geometry, Geometry], None, Double),
+            ("geo.intersects", vec![Geography, Geography], None, Boolean),
+            ("geo.intersects", vec![Geometry, Geometry], None, Boolean),
+            ("geo.length", vec![Geography], None, Double),
+            ("geo.length", vec![Geometry], None, Double),
+        ];
+
+        // Collection functions, for collections of every primitive type.
+        let primitives = [
+            Boolean, Byte, SByte, Int32, Int64, Decimal, Single, Double, Uuid, DateTime, Date,
+            Time, Duration, String, Binary, Geography, Geometry,
+        ];
+        let collection_functions = primitives.into_iter().flat_map(|member| {
+            let collection = Collection(Box::new(member.clone()));
+
+            [
+                (
+                    "concat",
+                    vec![collection.clone(), collection.clone()],
+                    None,
+                    collection.clone(),
+                ),
+                ("contains", vec![collection.clone(), member], None, Boolean),
+                (
+                    "contains",
+                    vec![collection.clone(), collection.clone()],
+                    None,
+                    Boolean,
+                ),
+                (
+                    "endswith",
+                    vec![collection.clone(), collection.clone()],
+                    None,
+                    Boolean,
+                ),
+                (
+                    "hassubset",
+                    vec![collection.clone(), collection.clone()],
+                    None,
+                    Boolean,
+                ),
+                (
+                    "hassubsequence",
+                    vec![collection.clone(), collection.clone()],
+                    None,
+                    Boolean,
+                ),
+                (
+                    "indexof",
+                    vec![collection.clone(), collection.clone()],
+                    None,
+                    Int32,
+                ),
+                ("length", vec![collection.clone()], None, Int32),
+                (
+                    "startswith",
+                    vec![collection.clone(), collection.clone()],
+                    None,
+                    Boolean,
+                ),
+                (
+                    "substring",
+                    vec![collection.clone(), Int32],
+                    None,
+                    collection.clone(),
+                ),
+                (
+                    "substring",
+                    vec![collection.clone(), Int32, Int32],
+                    None,
+                    collection,
+                ),
+            ]
+        });
+
+        functions
+            .into_iter()
+            .chain(collection_functions)
+            .fold(Self::default(), |map, (name, args, variadic, ret)| {
+                map.with_function(name, args, variadic, ret)
+            })
+    }
+
+    /// Declares a function by its name with its argument types, optional
+    /// variadic argument type, and return type. Declaring a function that
+    /// already exists adds an overload to it.
+    pub fn with_function<I>(
+        mut self,
+        name: impl Into<String>,
+        args: I,
+        variadic: Option<Type>,
+        ret: Type,
+    ) -> Self
+    where
+        I: IntoIterator<Item = Type>,
+    {
+        self.0
+            .entry(name.into())
+            .or_default()
+            .push(FunctionSignature {
+                args: args.into_iter().collect(),
+                variadic,
+                ret,
+            });
+        self
+    }
+
+    /// Combines the functions of both maps, where functions declared in
+    /// both add the overloads of `other` after the existing ones.
+    pub fn merge(mut self, other: FunctionsTypeMap) -> Self {
+        for (name, signatures) in other.0 {
+            self.0.entry(name).or_default().extend(signatures);
+        }
+        self
+    }
+}
@@ -1,5 +1,6 @@
-use super::{Expr, Value};
-use chrono::SecondsFormat::Millis;
+use super::{base64, ArithmeticOperator, CompareOperator, Expr, Position, Shape, Spatial, Value};
+use chrono::SecondsFormat::{AutoSi, Millis};
+use chrono::{TimeDelta, Timelike};
 use std::fmt::{self, Write};
 
 /// Converts an `Expr` AST to its corresponding OData `$filter` string representation.
@@ -75,14 +76,50 @@ fn write_string<W: Write>(writer: &mut W, expr: &Expr, recursive_call: bool) ->
 
         // Handle comparison expressions.
         Expr::Compare(lhs, op, rhs) => {
-            write_string(writer, lhs, true)?;
+            write_operand(writer, lhs, op.precedence() - 1)?;
+            write!(writer, " {op} ")?;
+            write_operand(writer, rhs, op.precedence())
+        }
+
+        // Handle arithmetic expressions.
+        Expr::Arithmetic(lhs, op, rhs) => {
+            write_operand(writer, lhs, op.precedence() - 1)?;
             write!(writer, " {op} ")?;
-            write_string(writer, rhs, true)
+            write_operand(writer, rhs, op.precedence())
+        }
+
+        // Handle arithmetic negation. Numeric literals are wrapped in
+        // parentheses so they are not read back as a negative number.
+        Expr::Negate(operand) => {
+            write!(writer, "-")?;
+
+            if let Expr::Value(
+                Value::Number(_)
+                | Value::Int64(_)
+                | Value::Decimal(_)
+                | Value::Double(_)
+                | Value::Single(_)
+                | Value::Infinity,
+            ) = operand.as_ref()
+            {
+                write!(writer, "(")?;
+                write_string(writer, operand, true)?;
+                write!(writer, ")")
+            } else {
+                write_operand(writer, operand, UNARY_PRECEDENCE - 1)
+            }
+        }
+
+        // Handle has expressions.
+        Expr::Has(lhs, rhs) => {
+            write_operand(writer, lhs, HAS_PRECEDENCE - 1)?;
+            write!(writer, " has ")?;
+            write_operand(writer, rhs, HAS_PRECEDENCE)
         }
 
         // Handle IN expressions.
         Expr::In(lhs, values) => {
-            write_string(writer, lhs, true)?;
+            write_operand(writer, lhs, HAS_PRECEDENCE - 1)?;
             write!(writer, " in (")?;
 
             for (i, value) in values.iter().enumerate() {
@@ -96,10 +133,30 @@ fn write_string<W: Write>(writer: &mut W, expr: &Expr, recursive_call: bool) ->
             write!(writer, ")")
         }
 
+        // Handle IN expressions with a collection.
+        Expr::InCollection(lhs, collection) => {
+            write_operand(writer, lhs, HAS_PRECEDENCE - 1)?;
+            write!(writer, " in ")?;
+            write_operand(writer, collection, HAS_PRECEDENCE)
+        }
+
         // Handle logical NOT expressions.
         Expr::Not(expr) => {
             write!(writer, "not ")?;
-            write_string(writer, expr, true)
+            write_operand(writer, expr, UNARY_PRECEDENCE - 1)
+        }
+
+        // Handle lambda operators.
+        Expr::Lambda(collection, op, predicate) => {
+            write_string(writer, collection, true)?;
+            write!(writer, "/{op}(")?;
+
+            if let Some((variable, predicate)) = predicate {
+                write!(writer, "{variable}: ")?;
+                write_string(writer, predicate, false)?;
+            }
+
+            write!(writer, ")")
         }
 
         // Handle function calls.
@@ -117,14 +174,93 @@ fn write_string<W: Write>(writer: &mut W, expr: &Expr, recursive_call: bool) ->
             write!(writer, ")")
         }
 
+        // Handle parameter aliases.
+        Expr::Alias(name) => write!(writer, "@{name}"),
+
         // Handle identifiers.
         Expr::Identifier(name) => write!(writer, "{name}"),
 
+        // Handle member paths.
+        Expr::Path(segments) => write!(writer, "{}", segments.join("/")),
+
+        // Handle type names.
+        Expr::TypeName(name) => write!(writer, "{name}"),
+
         // Handle values.
         Expr::Value(value) => write_value(writer, value),
     }
 }
 
+/// Precedence of the equality operators `eq` and `ne`.
+const EQUALITY_PRECEDENCE: u8 = 3;
+
+/// Precedence of the relational operators.
+const RELATIONAL_PRECEDENCE: u8 = 4;
+
+/// Precedence of `not` and unary minus, which bind tighter than any binary
+/// operator other than `has` and `in`.
+const UNARY_PRECEDENCE: u8 = 7;
+
+/// Precedence of the `has` and `in` operators.
+const HAS_PRECEDENCE: u8 = 8;
+
+/// Writes an operand of an operator, only wrapping it in parentheses when
+/// it would otherwise bind to a neighbouring operator.
+///
+/// Nested operations are wrapped when their precedence is not above `min_precedence`,
+/// which allows left-associative chains to be written without parentheses.
+fn write_operand<W: Write>(writer: &mut W, operand: &Expr, min_precedence: u8) -> fmt::Result {
+    let needs_parentheses = match operand {
+        Expr::Compare(_, op, _) => op.precedence() <= min_precedence,
+        Expr::Arithmetic(_, op, _) => op.precedence() <= min_precedence,
+        Expr::Not(_) | Expr::Negate(_) => UNARY_PRECEDENCE <= min_precedence,
+        Expr::Has(..) | Expr::In(..) | Expr::InCollection(..) => HAS_PRECEDENCE <= min_precedence,
+        // Logical joins already wrap themselves in parentheses.
+        Expr::Or(..) | Expr::And(..) => false,
+        Expr::Lambda(..)
+        | Expr::Function(..)
+        | Expr::Alias(_)
+        | Expr::Identifier(_)
+        | Expr::Path(_)
+        | Expr::TypeName(_)
+        | Expr::Value(_) => false,
+    };
+
+    if needs_parentheses {
+        write!(writer, "(")?;
+        write_string(writer, operand, true)?;
+        write!(writer, ")")
+    } else {
+        write_string(writer, operand, true)
+    }
+}
+
+impl CompareOperator {
+    /// Returns how tightly the operator binds, where a higher number binds tighter.
+    fn precedence(&self) -> u8 {
+        match self {
+            CompareOperator::Equal | CompareOperator::NotEqual => EQUALITY_PRECEDENCE,
+            CompareOperator::GreaterThan
+            | CompareOperator::GreaterOrEqual
+            | CompareOperator::LessThan
+            | CompareOperator::LessOrEqual => RELATIONAL_PRECEDENCE,
+        }
+    }
+}
+
+impl ArithmeticOperator {
+    /// Returns how tightly the operator binds, where a higher number binds tighter.
+    fn precedence(&self) -> u8 {
+        match self {
+            ArithmeticOperator::Add | ArithmeticOperator::Sub => 5,
+            ArithmeticOperator::Mul
+            | ArithmeticOperator::Div
+            | ArithmeticOperator::DivBy
+            | ArithmeticOperator::Mod => 6,
+        }
+    }
+}
+
 /// Writes a `Value` to a writer.
 ///
 /// A `fmt::Result` indicating the success or failure of the write operation.
@@ -136,14 +272,43 @@ fn write_value<W: Write>(writer: &mut W, value: &Value) -> fmt::Result {
         // Handle boolean values.
         Value::Bool(b) => write!(writer, "{b}"),
 
-        // Handle numeric values.
-        Value::Number(n) => write!(writer, "{n}"),
+        // Handle numeric values, with the suffix or exponent that keeps their
+        // type. Digits are written in full, since an exponent makes a double.
+        Value::Number(n) => n.write_plain_string(writer),
+        Value::Int64(n) => write!(writer, "{n}L"),
+        Value::Decimal(n) => {
+            n.write_plain_string(writer)?;
+            write!(writer, "m")
+        }
+        Value::Double(n) => n.normalized().write_scientific_notation(writer),
+        Value::Single(n) => {
+            n.write_plain_string(writer)?;
+            write!(writer, "f")
+        }
+        Value::Infinity => write!(writer, "INF"),
+        Value::NegativeInfinity => write!(writer, "-INF"),
+        Value::NaN => write!(writer, "NaN"),
 
         // Handle UUID values.
         Value::Uuid(id) => write!(writer, "{id}"),
 
-        // Handle datetime values.
-        Value::DateTime(dt) => write!(writer, "{}", dt.to_rfc3339_opts(Millis, true)),
+        // Handle datetime values, keeping their offset or named time zone.
+        Value::DateTime(dt, zone) => {
+            // Milliseconds are always written unless more precision is needed.
+            let precision = if dt.nanosecond() % 1_000_000 == 0 {
+                Millis
+            } else {
+                AutoSi
+            };
+
+            match zone {
+                Some(zone) => {
+                    let local = dt.naive_local().and_utc().to_rfc3339_opts(precision, true);
+                    write!(writer, "{}{}", local.trim_end_matches('Z'), zone.name())
+                }
+                None => write!(writer, "{}", dt.to_rfc3339_opts(precision, true)),
+            }
+        }
 
         // Handle date values.
         Value::Date(d) => write!(writer, "{d}"),
@@ -151,7 +316,211 @@ fn write_value<W: Write>(writer: &mut W, value: &Value) -> fmt::Result {
         // Handle time values.
         Value::Time(t) => write!(writer, "{t}"),
 
+        // Handle duration values.
+        Value::Duration(d) => write_duration(writer, d),
+
         // Handle string values, escaping single quotes.
         Value::String(s) => write!(writer, "'{}'", s.replace('\'', "''")),
+
+        // Handle binary values as base64url.
+        Value::Binary(bytes) => write!(writer, "binary'{}'", base64::encode(bytes)),
+
+        // Handle spatial values as well-known text.
+        Value::Geography(spatial) => write_spatial(writer, "geography", spatial),
+        Value::Geometry(spatial) => write_spatial(writer, "geometry", spatial),
+
+        // Handle collection and complex values as JSON.
+        Value::Collection(values) => {
+            write!(writer, "[")?;
+
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    write!(writer, ",")?;
+                }
+
+                write_json_value(writer, value)?;
+            }
+
+            write!(writer, "]")
+        }
+        Value::Complex(members) => {
+            write!(writer, "{{")?;
+
+            for (i, (name, value)) in members.iter().enumerate() {
+                if i > 0 {
+                    write!(writer, ",")?;
+                }
+
+                write_json_string(writer, name)?;
+                write!(writer, ":")?;
+                write_json_value(writer, value)?;
+            }
+
+            write!(writer, "}}")
+        }
+
+        // Handle enum values, joining flag members with commas.
+        Value::Enum { type_name, members } => {
+            write!(writer, "{type_name}'{}'", members.join(","))
+        }
+    }
+}
+
+/// Writes a duration as an ISO 8601 duration literal, such as `duration'P1DT2H30M'`.
+fn write_duration<W: Write>(writer: &mut W, duration: &TimeDelta) -> fmt::Result {
+    let sign = if *duration < TimeDelta::zero() {
+        "-"
+    } else {
+        ""
+    };
+    let duration = duration.abs();
+
+    let total_seconds = duration.num_seconds();
+    let nanoseconds = duration.subsec_nanos();
+    let days = total_seconds / 86_400;
+    let hours = total_seconds / 3_600 % 24;
+    let minutes = total_seconds / 60 % 60;
+    let seconds = total_seconds % 60;
+
+    write!(writer, "duration'{sign}P")?;
+
+    if days > 0 {
+        write!(writer, "{days}D")?;
+    }
+
+    if hours > 0 || minutes > 0 || seconds > 0 || nanoseconds > 0 || days == 0 {
+        write!(writer, "T")?;
+
+        if hours > 0 {
+            write!(writer, "{hours}H")?;
+        }
+
+        if minutes > 0 {
+            write!(writer, "{minutes}M")?;
+        }
+
+        if seconds > 0 || nanoseconds > 0 || total_seconds == 0 {
+            write!(writer, "{seconds}")?;
+
+            if nanoseconds > 0 {
+                let fraction = format!("{nanoseconds:09}");
+                write!(writer, ".{}", fraction.trim_end_matches('0'))?;
+            }
+
+            write!(writer, "S")?;
+        }
+    }
+
+    write!(writer, "'")
+}
+
+/// Writes a member of a collection or complex value, where strings are
+/// written as JSON strings in double quotes.
+fn write_json_value<W: Write>(writer: &mut W, value: &Value) -> fmt::Result {
+    match value {
+        Value::String(s) => write_json_string(writer, s),
+        value => write_value(writer, value),
     }
 }
+
+/// Writes a JSON string in double quotes, escaping quotes, backslashes and
+/// control characters.
+fn write_json_string<W: Write>(writer: &mut W, s: &str) -> fmt::Result {
+    write!(writer, "\"")?;
+
+    for c in s.chars() {
+        match c {
+            '"' => write!(writer, "\\\"")?,
+            '\\' => write!(writer, "\\\\")?,
+            '\n' => write!(writer, "\\n")?,
+            '\r' => write!(writer, "\\r")?,
+            '\t' => write!(writer, "\\t")?,
+            c if c.is_control() => write!(writer, "\\u{:04x}", c as u32)?,
+            c => write!(writer, "{c}")?,
+        }
+    }
+
+    write!(writer, "\"")
+}
+
+/// Writes a spatial value as well-known text, such as `geography'SRID=4326;POINT(-73.5 45.5)'`.
+fn write_spatial<W: Write>(writer: &mut W, prefix: &str, spatial: &Spatial) -> fmt::Result {
+    write!(writer, "{prefix}'")?;
+
+    if let Some(srid) = spatial.srid {
+        write!(writer, "SRID={srid};")?;
+    }
+
+    write_shape(writer, &spatial.shape)?;
+    write!(writer, "'")
+}
+
+/// Writes the well-known text of a shape.
+fn write_shape<W: Write>(writer: &mut W, shape: &Shape) -> fmt::Result {
+    match shape {
+        Shape::Point(p) => {
+            write!(writer, "POINT(")?;
+            write_position(writer, p)?;
+            write!(writer, ")")
+        }
+        Shape::LineString(l) => {
+            write!(writer, "LINESTRING")?;
+            write_list(writer, l, write_position)
+        }
+        Shape::Polygon(p) => {
+            write!(writer, "POLYGON")?;
+            write_polygon(writer, p)
+        }
+        Shape::MultiPoint(p) => {
+            write!(writer, "MULTIPOINT")?;
+            write_list(writer, p, |writer, p| {
+                write!(writer, "(")?;
+                write_position(writer, p)?;
+                write!(writer, ")")
+            })
+        }
+        Shape::MultiLineString(l) => {
+            write!(writer, "MULTILINESTRING")?;
+            write_list(writer, l, |writer, l| write_list(writer, l, write_position))
+        }
+        Shape::MultiPolygon(p) => {
+            write!(writer, "MULTIPOLYGON")?;
+            write_list(writer, p, |writer, p| write_polygon(writer, p))
+        }
+        Shape::Collection(s) => {
+            write!(writer, "COLLECTION")?;
+            write_list(writer, s, |writer, s| write_shape(writer, s))
+        }
+    }
+}
+
+/// Writes the rings of a polygon.
+fn write_polygon<W: Write>(writer: &mut W, rings: &[Vec<Position>]) -> fmt::Result {
+    write_list(writer, rings, |writer, ring| {
+        write_list(writer, ring, write_position)
+    })
+}
+
+/// Writes a position as its coordinates separated by a space.
+fn write_position<W: Write>(writer: &mut W, position: &Position) -> fmt::Result {
+    write!(writer, "{} {}", position.x, position.y)
+}
+
+/// Writes items separated by commas within parentheses.
+fn write_list<W: Write, T>(
+    writer: &mut W,
+    items: &[T],
+    write_item: impl Fn(&mut W, &T) -> fmt::Result,
+) -> fmt::Result {
+    write!(writer, "(")?;
+
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            write!(writer, ",")?;
+        }
+
+        write_item(writer, item)?;
+    }
+
+    write!(writer, ")")
+}
@@ -1,4 +1,8 @@
-use super::{Expr, FunctionsTypeMap, IdentifiersTypeMap, Type, ValidationError, Value};
+use super::{
+    ArithmeticOperator, CompareOperator, Expr, ExprSpans, FunctionSignature, FunctionsTypeMap,
+    IdentifiersTypeMap, NodeValidationError, SpannedValidationError, Type, ValidationError, Value,
+};
+use bigdecimal::{BigDecimal, ToPrimitive};
 use std::iter::repeat;
 
 impl Expr {
@@ -42,34 +46,190 @@ impl Expr {
     /// use odata_params::filters::{Expr, FunctionsTypeMap, IdentifiersTypeMap, Type};
     ///
     /// let mut id_map = HashMap::new();
-    /// id_map.insert("value".to_string(), Type::Number);
+    /// id_map.insert("value".to_string(), Type::Decimal);
     /// let identifiers = IdentifiersTypeMap::from(id_map);
     ///
     /// let mut func_map = HashMap::new();
     /// func_map.insert(
     ///     "sum".to_string(),
-    ///     (vec![Type::Number], None, Type::Number),
+    ///     (vec![Type::Decimal], None, Type::Decimal),
     /// );
     /// let functions = FunctionsTypeMap::from(func_map);
     ///
     /// let expr = Expr::Function("sum".to_string(), vec![Expr::Identifier("value".to_string())]);
     ///
-    /// assert_eq!(expr.validate(&identifiers, &functions), Ok(Type::Number));
+    /// assert_eq!(expr.validate(&identifiers, &functions), Ok(Type::Decimal));
     /// ```
     pub fn validate(
         &self,
         identifiers: &IdentifiersTypeMap,
         functions: &FunctionsTypeMap,
     ) -> Result<Type, ValidationError> {
-        match self {
+        self.validate_all(identifiers, functions)
+            .map_err(|mut errors| errors.swap_remove(0).error)
+    }
+
+    /// Validates the types within the expression like `validate`, using the
+    /// locations from `parse_str_with_spans` to point at the expression
+    /// that caused the error.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use odata_params::filters::{parse_str_with_spans, FunctionsTypeMap, IdentifiersTypeMap, Span, Type};
+    ///
+    /// let mut id_map = HashMap::new();
+    /// id_map.insert("name".to_string(), Type::String);
+    /// let identifiers = IdentifiersTypeMap::from(id_map);
+    /// let functions = FunctionsTypeMap::from(HashMap::new());
+    ///
+    /// let filter = "name eq 'John' and age gt 30";
+    /// let (expr, spans) = parse_str_with_spans(filter).expect("valid filter tree");
+    ///
+    /// let error = expr
+    ///     .validate_with_spans(&spans, &identifiers, &functions)
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(error.span, Span { start: 19, end: 22 });
+    /// assert_eq!(&filter[error.span.start..error.span.end], "age");
+    /// ```
+    pub fn validate_with_spans(
+        &self,
+        spans: &ExprSpans,
+        identifiers: &IdentifiersTypeMap,
+        functions: &FunctionsTypeMap,
+    ) -> Result<Type, SpannedValidationError> {
+        self.validate_all(identifiers, functions)
+            .map_err(|mut errors| {
+                let NodeValidationError { path, error } = errors.swap_remove(0);
+
+                // Fall back to the closest known ancestor when the spans do
+                // not describe the expression down to the offending node.
+                let span = (0..=path.len())
+                    .rev()
+                    .find_map(|depth| spans.get(&path[..depth]))
+                    .unwrap_or(spans)
+                    .span;
+
+                SpannedValidationError { error, span }
+            })
+    }
+
+    /// Validates the types within the whole expression, collecting every
+    /// error instead of stopping at the first one.
+    ///
+    /// Each error is tagged with the path to the offending expression, which
+    /// can be used with `ExprSpans::get` to locate it in the source. Errors
+    /// are listed in the order they are found, so the first one is the error
+    /// returned by `validate`. Expressions depending on an invalid expression
+    /// are not reported again.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use odata_params::filters::{parse_str, FunctionsTypeMap, IdentifiersTypeMap, NodeValidationError, Type, ValidationError};
+    ///
+    /// let mut id_map = HashMap::new();
+    /// id_map.insert("name".to_string(), Type::String);
+    /// let identifiers = IdentifiersTypeMap::from(id_map);
+    /// let functions = FunctionsTypeMap::from(HashMap::new());
+    ///
+    /// let expr = parse_str("age gt 30 and name eq 'John' and city eq 'Paris'").expect("valid filter tree");
+    ///
+    /// assert_eq!(
+    ///     expr.validate_all(&identifiers, &functions),
+    ///     Err(vec![
+    ///         NodeValidationError {
+    ///             path: vec![0, 0, 0],
+    ///             error: ValidationError::UndefinedIdentifier { name: "age".to_string() },
+    ///         },
+    ///         NodeValidationError {
+    ///             path: vec![1, 0],
+    ///             error: ValidationError::UndefinedIdentifier { name: "city".to_string() },
+    ///         },
+    ///     ])
+    /// );
+    /// ```
+    pub fn validate_all(
+        &self,
+        identifiers: &IdentifiersTypeMap,
+        functions: &FunctionsTypeMap,
+    ) -> Result<Type, Vec<NodeValidationError>> {
+        let mut validator = Validator {
+            identifiers,
+            functions,
+            variables: Vec::new(),
+            path: Vec::new(),
+            errors: Vec::new(),
+        };
+
+        match validator.check(self).map(split_nullable) {
+            Some((expr_type, _)) if validator.errors.is_empty() => Ok(expr_type),
+            _ => Err(validator.errors),
+        }
+    }
+}
+
+/// Walks an expression to find its type, collecting every error found along the way.
+struct Validator<'a> {
+    identifiers: &'a IdentifiersTypeMap,
+    functions: &'a FunctionsTypeMap,
+
+    /// Lambda range variables in scope, innermost last. A variable has no
+    /// type when its collection is invalid.
+    variables: Vec<(String, Option<Type>)>,
+
+    /// Path from the root to the expression being validated.
+    path: Vec<usize>,
+
+    errors: Vec<NodeValidationError>,
+}
+
+impl Validator<'_> {
+    /// Validates a child of the current expression.
+    fn child(&mut self, index: usize, expr: &Expr) -> Option<Type> {
+        self.child_nullable(index, expr)
+            .map(|(expr_type, _)| expr_type)
+    }
+
+    /// Validates a child of the current expression, also returning whether
+    /// it can be null.
+    fn child_nullable(&mut self, index: usize, expr: &Expr) -> Option<(Type, bool)> {
+        self.path.push(index);
+        let expr_type = self.check(expr);
+        self.path.pop();
+        expr_type.map(split_nullable)
+    }
+
+    /// Records an error for the current expression.
+    fn error(&mut self, error: ValidationError) -> Option<Type> {
+        self.errors.push(NodeValidationError {
+            path: self.path.clone(),
+            error,
+        });
+        None
+    }
+
+    /// Records an error for a child of the current expression.
+    fn child_error(&mut self, index: usize, error: ValidationError) -> Option<Type> {
+        self.path.push(index);
+        self.error(error);
+        self.path.pop();
+        None
+    }
+
+    /// Returns the type of an expression, or `None` if an error was
+    /// recorded for it or for an expression it depends on. The type is
+    /// wrapped in `Type::Nullable` when the expression can be null.
+    fn check(&mut self, expr: &Expr) -> Option<Type> {
+        match expr {
             Expr::Or(lhs, rhs) | Expr::And(lhs, rhs) => {
-                let lhs_type = Self::validate(lhs, identifiers, functions)?;
-                let rhs_type = Self::validate(rhs, identifiers, functions)?;
+                let lhs_type = self.child(0, lhs);
+                let rhs_type = self.child(1, rhs);
+                let (lhs_type, rhs_type) = (lhs_type?, rhs_type?);
 
                 if lhs_type == Type::Boolean && rhs_type == Type::Boolean {
-                    Ok(Type::Boolean)
+                    Some(Type::Boolean)
                 } else {
-                    Err(ValidationError::LogicalJoinRequiresBooleans {
+                    self.error(ValidationError::LogicalJoinRequiresBooleans {
                         lhs: lhs_type,
                         rhs: rhs_type,
                     })
@@ -77,115 +237,856 @@ impl Expr {
             }
 
             Expr::Not(inner) => {
-                let inner_type = Self::validate(inner, identifiers, functions)?;
+                let inner_type = self.child(0, inner)?;
 
                 if inner_type == Type::Boolean {
-                    Ok(Type::Boolean)
+                    Some(Type::Boolean)
                 } else {
-                    Err(ValidationError::LogicalNotRequiresBoolean { given: inner_type })
+                    self.error(ValidationError::LogicalNotRequiresBoolean { given: inner_type })
+                }
+            }
+
+            Expr::Compare(lhs, op, rhs) => {
+                let lhs_type = self.child_nullable(0, lhs);
+                let rhs_type = self.child_nullable(1, rhs);
+                let ((lhs_type, lhs_nullable), (rhs_type, rhs_nullable)) = (lhs_type?, rhs_type?);
+                let rhs_type = self.conform(rhs_type, &lhs_type);
+                let lhs_type = self.conform(lhs_type, &rhs_type);
+
+                let rhs_type = match conform_number(rhs_type, rhs, &lhs_type) {
+                    Ok(rhs_type) => rhs_type,
+                    Err(error) => return self.error(error),
+                };
+                let lhs_type = match conform_number(lhs_type, lhs, &rhs_type) {
+                    Ok(lhs_type) => lhs_type,
+                    Err(error) => return self.error(error),
+                };
+
+                let is_equality = matches!(op, CompareOperator::Equal | CompareOperator::NotEqual);
+
+                match (&lhs_type, &rhs_type) {
+                    (Type::Null, given) | (given, Type::Null)
+                        if is_equality && !(lhs_nullable && rhs_nullable) =>
+                    {
+                        self.error(ValidationError::ComparingNullWithNonNullable {
+                            given: given.clone(),
+                        })
+                    }
+
+                    (Type::Null, _) | (_, Type::Null) if is_equality => Some(Type::Boolean),
+
+                    _ if !is_equality && !is_ordered(&lhs_type) => {
+                        self.error(ValidationError::UnsupportedComparisonOperator {
+                            operator: *op,
+                            given: lhs_type,
+                        })
+                    }
+
+                    _ if !is_equality && !is_ordered(&rhs_type) => {
+                        self.error(ValidationError::UnsupportedComparisonOperator {
+                            operator: *op,
+                            given: rhs_type,
+                        })
+                    }
+
+                    _ if can_compare(&lhs_type, lhs, &rhs_type, rhs, is_equality) => {
+                        Some(Type::Boolean)
+                    }
+
+                    _ => self.error(ValidationError::ComparingIncompatibleTypes {
+                        lhs: lhs_type,
+                        rhs: rhs_type,
+                    }),
                 }
             }
 
-            Expr::Compare(lhs, _op, rhs) => {
-                let lhs_type = Self::validate(lhs, identifiers, functions)?;
-                let rhs_type = Self::validate(rhs, identifiers, functions)?;
+            Expr::Arithmetic(lhs, op, rhs) => {
+                let lhs_type = self.child_nullable(0, lhs);
+                let rhs_type = self.child_nullable(1, rhs);
+                let ((lhs_type, lhs_nullable), (rhs_type, rhs_nullable)) = (lhs_type?, rhs_type?);
 
-                if lhs_type == rhs_type {
-                    Ok(Type::Boolean)
+                if let Some(result_type) = arithmetic_type(*op, &lhs_type, &rhs_type) {
+                    // The result is null when either operand is null.
+                    Some(if lhs_nullable || rhs_nullable {
+                        result_type.nullable()
+                    } else {
+                        result_type
+                    })
                 } else {
-                    Err(ValidationError::ComparingIncompatibleTypes {
+                    self.error(ValidationError::ArithmeticOnIncompatibleTypes {
+                        operator: *op,
                         lhs: lhs_type,
                         rhs: rhs_type,
                     })
                 }
             }
 
+            Expr::Negate(inner) => {
+                let (inner_type, is_nullable) = self.child_nullable(0, inner)?;
+
+                if numeric_rank(&inner_type).is_some() || inner_type == Type::Duration {
+                    Some(if is_nullable {
+                        inner_type.nullable()
+                    } else {
+                        inner_type
+                    })
+                } else {
+                    self.error(ValidationError::NegationRequiresNumber { given: inner_type })
+                }
+            }
+
+            Expr::Has(lhs, rhs) => {
+                let lhs_type = self.child(0, lhs);
+                let rhs_type = self.child(1, rhs);
+                let (lhs_type, rhs_type) = (lhs_type?, rhs_type?);
+
+                match (&lhs_type, &rhs_type) {
+                    (Type::Enum(lhs_name), Type::Enum(rhs_name)) if lhs_name == rhs_name => {
+                        Some(Type::Boolean)
+                    }
+
+                    _ => self.error(ValidationError::HasRequiresMatchingEnums {
+                        lhs: lhs_type,
+                        rhs: rhs_type,
+                    }),
+                }
+            }
+
             Expr::In(lhs, values) => {
-                let lhs_type = Self::validate(lhs, identifiers, functions)?;
+                let lhs_type = self.child_nullable(0, lhs);
+                let mut is_valid = lhs_type.is_some();
 
-                for value in values {
-                    let value_type = Self::validate(value, identifiers, functions)?;
+                for (index, value) in values.iter().enumerate() {
+                    let value_type = match (&lhs_type, self.child(index + 1, value)) {
+                        (Some((lhs_type, _)), Some(value_type)) => {
+                            let value_type = self.conform(value_type, lhs_type);
 
-                    if lhs_type != value_type {
-                        return Err(ValidationError::ComparingIncompatibleTypes {
-                            lhs: lhs_type,
-                            rhs: value_type,
-                        });
+                            match conform_number(value_type, value, lhs_type) {
+                                Ok(value_type) => Some(value_type),
+                                Err(error) => {
+                                    is_valid = false;
+                                    self.child_error(index + 1, error);
+                                    continue;
+                                }
+                            }
+                        }
+                        (_, value_type) => value_type,
+                    };
+
+                    match (&lhs_type, value_type) {
+                        (Some((lhs_type, false)), Some(Type::Null)) => {
+                            is_valid = false;
+                            self.child_error(
+                                index + 1,
+                                ValidationError::ComparingNullWithNonNullable {
+                                    given: lhs_type.clone(),
+                                },
+                            );
+                        }
+
+                        (Some((_, true)), Some(Type::Null)) => {}
+
+                        (Some((lhs_type, _)), Some(value_type))
+                            if !can_compare(lhs_type, lhs, &value_type, value, true) =>
+                        {
+                            is_valid = false;
+                            self.child_error(
+                                index + 1,
+                                ValidationError::ComparingIncompatibleTypes {
+                                    lhs: lhs_type.clone(),
+                                    rhs: value_type,
+                                },
+                            );
+                        }
+
+                        (_, None) => is_valid = false,
+
+                        _ => {}
                     }
                 }
 
-                Ok(Type::Boolean)
+                is_valid.then_some(Type::Boolean)
+            }
+
+            Expr::InCollection(lhs, collection) => {
+                let lhs_type = self.child(0, lhs);
+                let collection_type = self.child(1, collection);
+                let (lhs_type, collection_type) = (lhs_type?, collection_type?);
+
+                let Type::Collection(member_type) = collection_type else {
+                    return self.error(ValidationError::InRequiresCollection {
+                        given: collection_type,
+                    });
+                };
+
+                // Empty collections, or ones holding only nulls, have no
+                // member type of their own.
+                match split_nullable(*member_type) {
+                    (Type::Null, _) => Some(Type::Boolean),
+                    (member_type, _)
+                        if can_compare(&lhs_type, lhs, &member_type, collection, true) =>
+                    {
+                        Some(Type::Boolean)
+                    }
+                    (member_type, _) => self.error(ValidationError::ComparingIncompatibleTypes {
+                        lhs: lhs_type,
+                        rhs: member_type,
+                    }),
+                }
+            }
+
+            Expr::Lambda(collection, op, predicate) => {
+                let member_type = match self.child(0, collection) {
+                    Some(Type::Collection(member_type)) => Some(*member_type),
+
+                    Some(collection_type) => {
+                        self.error(ValidationError::LambdaRequiresCollection {
+                            operator: *op,
+                            given: collection_type,
+                        })
+                    }
+
+                    None => None,
+                };
+
+                let Some((variable, predicate)) = predicate else {
+                    return member_type.map(|_| Type::Boolean);
+                };
+
+                self.variables
+                    .push((variable.to_owned(), member_type.clone()));
+                let predicate_type = self.child(1, predicate);
+                self.variables.pop();
+
+                let (_, predicate_type) = (member_type?, predicate_type?);
+
+                if predicate_type == Type::Boolean {
+                    Some(Type::Boolean)
+                } else {
+                    self.error(ValidationError::LambdaRequiresBooleanPredicate {
+                        operator: *op,
+                        given: predicate_type,
+                    })
+                }
             }
 
             Expr::Function(function, args) => {
-                let (types, variadic, ret) = functions.0.get(function).ok_or_else(|| {
-                    ValidationError::UndefinedFunction {
+                if let Some((operand, type_name)) = type_function_args(function, args) {
+                    return self.check_type_function(function, operand, type_name);
+                }
+
+                let Some(signatures) = self.functions.0.get(function) else {
+                    self.error(ValidationError::UndefinedFunction {
                         name: function.to_owned(),
+                    });
+
+                    // Arguments are still checked for their own errors.
+                    for (index, arg) in args.iter().enumerate() {
+                        self.child(index, arg);
                     }
-                })?;
 
-                println!(":: {types:?}, {variadic:?}, {args:?}");
+                    return None;
+                };
+
+                if let [signature] = signatures.as_slice() {
+                    return self.check_call(function, signature, args);
+                }
+
+                let (arg_types, are_nullable): (Vec<_>, Vec<_>) = args
+                    .iter()
+                    .enumerate()
+                    .map(|(index, arg)| self.child_nullable(index, arg))
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .collect::<Option<Vec<_>>>()?
+                    .into_iter()
+                    .unzip();
+
+                let accepts = |signature: &FunctionSignature| {
+                    let expected_types = signature
+                        .args
+                        .iter()
+                        .chain(signature.variadic.iter().cycle())
+                        .map(Some)
+                        .chain(repeat(None));
+
+                    let arg_types = arg_types
+                        .iter()
+                        .zip(args)
+                        .zip(expected_types)
+                        .map(|((given, arg), expected)| match expected {
+                            Some(expected) => {
+                                let given = self.conform(given.clone(), expected);
+                                conform_number(given.clone(), arg, expected).unwrap_or(given)
+                            }
+                            None => given.clone(),
+                        })
+                        .collect::<Vec<_>>();
+
+                    signature.accepts(&arg_types)
+                };
 
-                if (variadic.is_none() && types.len() != args.len())
-                    || (variadic.is_some() && types.len() > args.len())
-                {
-                    return Err(ValidationError::IncorrectFunctionArgumentsCount {
+                match signatures.iter().find(|s| accepts(s)) {
+                    Some(signature) => Some(signature.returned(&are_nullable)),
+                    None => self.error(ValidationError::NoMatchingFunctionOverload {
                         name: function.to_owned(),
-                        is_variadic: variadic.is_some(),
-                        expected: types.len(),
-                        given: args.len(),
+                        given: arg_types,
+                        candidates: signatures.clone(),
+                    }),
+                }
+            }
+
+            Expr::Alias(name) => self.error(ValidationError::UnresolvedAlias {
+                name: name.to_owned(),
+            }),
+
+            Expr::Identifier(identifier) => self.resolve_identifier(identifier),
+
+            Expr::Path(segments) => {
+                let Some((first, members)) = segments.split_first() else {
+                    return self.error(ValidationError::UndefinedIdentifier {
+                        name: String::new(),
                     });
+                };
+
+                let root_type = self.resolve_identifier(first)?;
+
+                match self.identifiers.resolve_members(root_type, members) {
+                    Ok(member_type) => Some(member_type),
+                    Err(error) => self.error(error),
                 }
+            }
 
-                // It should be safe to setup an infinite chain of nulls when
-                // `variadic` is not set since we should have already exited
-                // early when `variadic` is None and `types` have a different
-                // length than the given arguments.
-                //
-                // This is needed to have consistent types without needing to
-                // collect eagerly. The `.zip` is what keeps the infinite
-                // iterator fixed to the length of given arguments.
-                let types = args.iter().zip(
-                    types
-                        .iter()
-                        .copied()
-                        .chain(repeat(variadic.unwrap_or(Type::Null))),
-                );
+            Expr::TypeName(name) => self.error(ValidationError::UnexpectedTypeName {
+                name: name.to_owned(),
+            }),
 
-                for (index, (arg, expected_type)) in types.enumerate() {
-                    let arg_type = Self::validate(arg, identifiers, functions)?;
+            Expr::Value(value) => self.value_type(value),
+        }
+    }
+
+    /// Resolves the type of a constant value, where collections take the
+    /// promoted type of their elements and complex values the types of
+    /// their members.
+    fn value_type(&mut self, value: &Value) -> Option<Type> {
+        match value {
+            Value::Null => Some(Type::Null),
+            Value::Bool(_) => Some(Type::Boolean),
+            Value::Number(n) => Some(number_type(n)),
+            Value::Int64(_) => Some(Type::Int64),
+            Value::Decimal(_) => Some(Type::Decimal),
+            Value::Double(_) | Value::Infinity | Value::NegativeInfinity | Value::NaN => {
+                Some(Type::Double)
+            }
+            Value::Single(_) => Some(Type::Single),
+            Value::Uuid(_) => Some(Type::Uuid),
+            Value::DateTime(..) => Some(Type::DateTime),
+            Value::Date(_) => Some(Type::Date),
+            Value::Time(_) => Some(Type::Time),
+            Value::Duration(_) => Some(Type::Duration),
+            Value::String(_) => Some(Type::String),
+            Value::Binary(_) => Some(Type::Binary),
+            Value::Geography(_) => Some(Type::Geography),
+            Value::Geometry(_) => Some(Type::Geometry),
+            Value::Enum { type_name, members } => {
+                let Some(declared) = self.identifiers.enum_members(type_name) else {
+                    return self.error(ValidationError::UndefinedEnumType {
+                        name: type_name.to_owned(),
+                    });
+                };
+
+                if let Some(member) = members.iter().find(|m| !declared.contains(m)) {
+                    return self.error(ValidationError::UndefinedEnumMember {
+                        type_name: type_name.to_owned(),
+                        member: member.to_owned(),
+                    });
+                }
+
+                Some(Type::Enum(type_name.to_owned()))
+            }
+            Value::Collection(values) => {
+                let mut element_type = Type::Null;
+                let mut is_nullable = false;
 
-                    if arg_type != expected_type {
-                        return Err(ValidationError::IncorrectFunctionArgumentType {
-                            name: function.to_owned(),
-                            position: index + 1,
-                            expected: expected_type,
-                            given: arg_type,
+                for value in values {
+                    let (value_type, value_nullable) = split_nullable(self.value_type(value)?);
+                    is_nullable |= value_nullable;
+
+                    element_type = match (element_type, value_type) {
+                        (Type::Null, value_type) => value_type,
+                        (element_type, Type::Null) => element_type,
+                        (element_type, value_type) if element_type == value_type => element_type,
+                        (element_type, value_type) => match promote(&element_type, &value_type) {
+                            Some(promoted) => promoted,
+                            None => {
+                                return self.error(
+                                    ValidationError::IncompatibleCollectionElements {
+                                        expected: element_type,
+                                        given: value_type,
+                                    },
+                                )
+                            }
+                        },
+                    };
+                }
+
+                Some(Type::Collection(Box::new(if is_nullable {
+                    element_type.nullable()
+                } else {
+                    element_type
+                })))
+            }
+            Value::Complex(members) => members
+                .iter()
+                .map(|(name, value)| Some((name.to_owned(), self.value_type(value)?)))
+                .collect::<Option<_>>()
+                .map(Type::Object),
+        }
+    }
+
+    /// Gives a literal the type it is used as, where a complex value takes
+    /// the expected complex type when all its members are declared
+    /// properties of it, and the elements of a collection take the element
+    /// type of the expected collection. Other types are returned as is.
+    fn conform(&self, given: Type, expected: &Type) -> Type {
+        match (given, expected) {
+            (given, Type::Nullable(expected)) => self.conform(given, expected),
+
+            (Type::Object(members), Type::Complex(type_name)) => {
+                let is_compatible =
+                    self.identifiers
+                        .complex_type(type_name)
+                        .is_some_and(|properties| {
+                            members.iter().all(|(name, member_type)| {
+                                properties.get(name).is_some_and(|property_type| {
+                                    converts_to(
+                                        &self.conform(member_type.clone(), property_type),
+                                        property_type,
+                                    )
+                                })
+                            })
                         });
-                    }
+
+                if is_compatible {
+                    expected.clone()
+                } else {
+                    Type::Object(members)
                 }
+            }
 
-                Ok(*ret)
+            (Type::Collection(element_type), Type::Collection(expected_element)) => {
+                let element_type = self.conform(*element_type, expected_element);
+
+                // Empty collections, or ones holding only nulls, have no
+                // element type of their own.
+                if element_type == Type::Null || converts_to(&element_type, expected_element) {
+                    expected.clone()
+                } else {
+                    Type::Collection(Box::new(element_type))
+                }
             }
 
-            Expr::Identifier(identifier) => {
-                identifiers.0.get(identifier).copied().ok_or_else(|| {
-                    ValidationError::UndefinedIdentifier {
-                        name: identifier.to_owned(),
-                    }
-                })
-            }
-
-            Expr::Value(value) => Ok(match value {
-                Value::Null => Type::Null,
-                Value::Bool(_) => Type::Boolean,
-                Value::Number(_) => Type::Number,
-                Value::Uuid(_) => Type::Uuid,
-                Value::DateTime(_) => Type::DateTime,
-                Value::Date(_) => Type::Date,
-                Value::Time(_) => Type::Time,
-                Value::String(_) => Type::String,
+            (given, _) => given,
+        }
+    }
+
+    /// Validates a call to a function with a single signature, reporting
+    /// the count and each argument that does not match it.
+    fn check_call(
+        &mut self,
+        function: &str,
+        signature: &FunctionSignature,
+        args: &[Expr],
+    ) -> Option<Type> {
+        let FunctionSignature {
+            args: types,
+            variadic,
+            ..
+        } = signature;
+
+        let mut is_valid = true;
+
+        if (variadic.is_none() && types.len() != args.len())
+            || (variadic.is_some() && types.len() > args.len())
+        {
+            is_valid = false;
+            self.error(ValidationError::IncorrectFunctionArgumentsCount {
+                name: function.to_owned(),
+                is_variadic: variadic.is_some(),
+                expected: types.len(),
+                given: args.len(),
+            });
+        }
+
+        // It should be safe to setup an infinite chain of `None` when
+        // `variadic` is not set since extra arguments have already
+        // been reported when `variadic` is None and `types` have a
+        // different length than the given arguments.
+        //
+        // This is needed to have consistent types without needing to
+        // collect eagerly. The `.zip` is what keeps the infinite
+        // iterator fixed to the length of given arguments.
+        let types = args.iter().zip(
+            types
+                .iter()
+                .cloned()
+                .map(Some)
+                .chain(repeat(variadic.clone())),
+        );
+
+        let mut are_nullable = Vec::with_capacity(args.len());
+
+        for (index, (arg, expected_type)) in types.enumerate() {
+            let Some((arg_type, is_nullable)) = self.child_nullable(index, arg) else {
+                is_valid = false;
+                continue;
+            };
+
+            are_nullable.push(is_nullable);
+
+            let Some(expected_type) = expected_type else {
+                continue;
+            };
+
+            let arg_type = self.conform(arg_type, &expected_type);
+            let arg_type = match conform_number(arg_type, arg, &expected_type) {
+                Ok(arg_type) => arg_type,
+                Err(error) => {
+                    is_valid = false;
+                    self.child_error(index, error);
+                    continue;
+                }
+            };
+
+            if !converts_to(&arg_type, &expected_type) {
+                is_valid = false;
+                self.child_error(
+                    index,
+                    ValidationError::IncorrectFunctionArgumentType {
+                        name: function.to_owned(),
+                        position: index + 1,
+                        expected: expected_type,
+                        given: arg_type,
+                    },
+                );
+            }
+        }
+
+        is_valid.then(|| signature.returned(&are_nullable))
+    }
+
+    /// Validates a call to the type function `cast` or `isof`, where the
+    /// operand can be of any type since a failed `cast` gives `null`.
+    fn check_type_function(
+        &mut self,
+        function: &str,
+        operand: Option<&Expr>,
+        type_name: &str,
+    ) -> Option<Type> {
+        let operand_type = operand.map(|operand| self.child(0, operand));
+
+        let Some(named_type) = self.identifiers.named_type(type_name) else {
+            return self.child_error(
+                usize::from(operand.is_some()),
+                ValidationError::UndefinedType {
+                    name: type_name.to_owned(),
+                },
+            );
+        };
+
+        if let Some(None) = operand_type {
+            return None;
+        }
+
+        Some(match function {
+            "cast" => named_type.nullable(),
+            _ => Type::Boolean,
+        })
+    }
+
+    /// Resolves the declared type of an identifier, where range variables
+    /// shadow identifiers of the same name.
+    fn resolve_identifier(&mut self, identifier: &str) -> Option<Type> {
+        if let Some((_, variable_type)) = self
+            .variables
+            .iter()
+            .rev()
+            .find(|(name, _)| name == identifier)
+        {
+            return variable_type.clone();
+        }
+
+        match self.identifiers.get(identifier) {
+            Some(identifier_type) => Some(identifier_type.clone()),
+            None => self.error(ValidationError::UndefinedIdentifier {
+                name: identifier.to_owned(),
             }),
         }
     }
 }
+
+impl IdentifiersTypeMap {
+    /// Resolves the type reached by following member names from a root type,
+    /// where every type along the way must be a declared complex type.
+    pub(crate) fn resolve_members(
+        &self,
+        root_type: Type,
+        members: &[String],
+    ) -> Result<Type, ValidationError> {
+        members.iter().try_fold(root_type, |current, member| {
+            let (current, is_nullable) = split_nullable(current);
+
+            let Type::Complex(type_name) = current else {
+                return Err(ValidationError::PropertyAccessRequiresComplexType {
+                    property: member.to_owned(),
+                    given: current,
+                });
+            };
+
+            let property_type = self
+                .complex_type(&type_name)
+                .ok_or_else(|| ValidationError::UndefinedComplexType {
+                    name: type_name.to_owned(),
+                })?
+                .get(member)
+                .cloned()
+                .ok_or_else(|| ValidationError::UndefinedProperty {
+                    type_name,
+                    property: member.to_owned(),
+                })?;
+
+            // Members of a nullable value are null along with it.
+            Ok(if is_nullable {
+                property_type.nullable()
+            } else {
+                property_type
+            })
+        })
+    }
+}
+
+impl FunctionSignature {
+    /// Returns the type returned for arguments that can be null or not,
+    /// which is nullable when a nullable argument is given for a parameter
+    /// that is not.
+    pub(crate) fn returned(&self, are_nullable: &[bool]) -> Type {
+        let parameters = self.args.iter().chain(self.variadic.iter().cycle());
+        let returns_null = are_nullable
+            .iter()
+            .zip(parameters)
+            .any(|(is_nullable, parameter)| {
+                *is_nullable && !matches!(parameter, Type::Nullable(_))
+            });
+
+        if returns_null {
+            self.ret.clone().nullable()
+        } else {
+            self.ret.clone()
+        }
+    }
+
+    /// Whether the signature accepts arguments of the given types.
+    pub(crate) fn accepts(&self, args: &[Type]) -> bool {
+        let count_matches = match self.variadic {
+            Some(_) => self.args.len() <= args.len(),
+            None => self.args.len() == args.len(),
+        };
+
+        count_matches
+            && args
+                .iter()
+                .zip(self.args.iter().chain(self.variadic.iter().cycle()))
+                .all(|(given, expected)| converts_to(given, expected))
+    }
+}
+
+/// Splits the arguments of a call to `cast` or `isof` into the optional
+/// operand and the name of the type, or returns `None` for other calls.
+fn type_function_args<'e>(function: &str, args: &'e [Expr]) -> Option<(Option<&'e Expr>, &'e str)> {
+    if !matches!(function, "cast" | "isof") {
+        return None;
+    }
+
+    match args {
+        [Expr::TypeName(type_name)] => Some((None, type_name)),
+        [operand, Expr::TypeName(type_name)] => Some((Some(operand), type_name)),
+        _ => None,
+    }
+}
+
+/// Returns how wide a numeric type is, where narrower types are implicitly
+/// promoted to wider ones, or `None` if the type is not numeric.
+fn numeric_rank(value_type: &Type) -> Option<u8> {
+    match value_type {
+        Type::Byte | Type::SByte => Some(0),
+        Type::Int32 => Some(1),
+        Type::Int64 => Some(2),
+        Type::Decimal => Some(3),
+        Type::Single => Some(4),
+        Type::Double => Some(5),
+        _ => None,
+    }
+}
+
+/// Whether values of the type can be ordered with `gt`, `ge`, `lt` and
+/// `le`, where other types only support `eq` and `ne`.
+fn is_ordered(value_type: &Type) -> bool {
+    numeric_rank(value_type).is_some()
+        || matches!(
+            value_type,
+            Type::DateTime
+                | Type::Date
+                | Type::Time
+                | Type::Duration
+                | Type::String
+                | Type::Enum(_)
+        )
+}
+
+/// Returns the type both numeric operands are promoted to, which is the
+/// wider of the two, or `None` if either operand is not numeric.
+fn promote(lhs: &Type, rhs: &Type) -> Option<Type> {
+    let (lhs_rank, rhs_rank) = (numeric_rank(lhs)?, numeric_rank(rhs)?);
+
+    // Bytes and signed bytes are both promoted to hold either range.
+    if lhs_rank == rhs_rank && lhs != rhs {
+        return Some(Type::Int32);
+    }
+
+    Some(if lhs_rank >= rhs_rank { lhs } else { rhs }.clone())
+}
+
+/// Returns the type resulting from an arithmetic operator, where numbers
+/// are promoted, at least to decimals for `divby`, durations are added to
+/// or subtracted from dates, dates are subtracted into durations, and
+/// durations are scaled by numbers.
+fn arithmetic_type(op: ArithmeticOperator, lhs: &Type, rhs: &Type) -> Option<Type> {
+    use ArithmeticOperator::*;
+
+    let is_numeric = |value_type: &Type| numeric_rank(value_type).is_some();
+
+    match (op, lhs, rhs) {
+        // Decimal division never truncates, so integers are promoted to decimals.
+        (DivBy, lhs, rhs) if is_numeric(lhs) && is_numeric(rhs) => {
+            promote(lhs, rhs).and_then(|promoted| promote(&promoted, &Type::Decimal))
+        }
+        (_, lhs, rhs) if is_numeric(lhs) && is_numeric(rhs) => promote(lhs, rhs),
+        (Add | Sub, Type::DateTime | Type::Date | Type::Duration, Type::Duration) => {
+            Some(lhs.clone())
+        }
+        (Sub, Type::DateTime, Type::DateTime) | (Sub, Type::Date, Type::Date) => {
+            Some(Type::Duration)
+        }
+        (Mul, Type::Duration, number) | (Mul, number, Type::Duration) if is_numeric(number) => {
+            Some(Type::Duration)
+        }
+        (Div | DivBy, Type::Duration, number) if is_numeric(number) => Some(Type::Duration),
+        _ => None,
+    }
+}
+
+/// Whether a value of the `given` type can be used where the `expected`
+/// type is required, either as is or through numeric promotion. Only
+/// nullable types accept `null`, while members of collections are matched
+/// regardless of whether they can be null.
+fn converts_to(given: &Type, expected: &Type) -> bool {
+    match (given, expected) {
+        (Type::Null, Type::Nullable(_)) => true,
+        (Type::Collection(given), Type::Collection(expected)) => {
+            split_nullable(*given.clone()).0 == split_nullable(*expected.clone()).0
+        }
+        (Type::Nullable(given), Type::Nullable(expected)) => converts_to(given, expected),
+        (_, Type::Nullable(expected)) => converts_to(given, expected),
+        _ => match (numeric_rank(given), numeric_rank(expected)) {
+            (Some(given_rank), Some(expected_rank)) => {
+                given_rank < expected_rank || given == expected
+            }
+            _ => given == expected,
+        },
+    }
+}
+
+/// Splits a type into its non-nullable type and whether it can be null.
+fn split_nullable(value_type: Type) -> (Type, bool) {
+    match value_type {
+        Type::Nullable(inner) => (*inner, true),
+        Type::Null => (Type::Null, true),
+        value_type => (value_type, false),
+    }
+}
+
+/// Whether two operands can be compared, either having the same type or
+/// both being numeric. An integer can never be equal to a fractional
+/// literal, such as in `Age eq 3.5`.
+fn can_compare(
+    lhs_type: &Type,
+    lhs: &Expr,
+    rhs_type: &Type,
+    rhs: &Expr,
+    is_equality: bool,
+) -> bool {
+    let is_integer = |value_type: &Type| {
+        matches!(
+            value_type,
+            Type::Byte | Type::SByte | Type::Int32 | Type::Int64
+        )
+    };
+
+    let is_fractional = |expr: &Expr| match expr {
+        Expr::Value(Value::Number(n) | Value::Decimal(n) | Value::Double(n) | Value::Single(n)) => {
+            !n.is_integer()
+        }
+        _ => false,
+    };
+
+    if is_equality
+        && (is_integer(lhs_type) && is_fractional(rhs)
+            || is_integer(rhs_type) && is_fractional(lhs))
+    {
+        return false;
+    }
+
+    lhs_type == rhs_type || promote(lhs_type, rhs_type).is_some()
+}
+
+/// Gives an integer literal written without a suffix the byte type it is
+/// used as, such as in `Flags eq 3`, or an error when its value is out of
+/// the range of that type. Other types are returned as is.
+fn conform_number(given: Type, expr: &Expr, expected: &Type) -> Result<Type, ValidationError> {
+    let Expr::Value(Value::Number(n)) = expr else {
+        return Ok(given);
+    };
+
+    let range = match expected {
+        Type::Nullable(expected) => return conform_number(given, expr, expected),
+        Type::Byte => i64::from(u8::MIN)..=i64::from(u8::MAX),
+        Type::SByte => i64::from(i8::MIN)..=i64::from(i8::MAX),
+        _ => return Ok(given),
+    };
+
+    if !n.is_integer() {
+        return Ok(given);
+    }
+
+    match n.to_i64() {
+        Some(value) if range.contains(&value) => Ok(expected.clone()),
+        _ => Err(ValidationError::NumberOutOfRange {
+            value: n.clone(),
+            expected: expected.clone(),
+        }),
+    }
+}
+
+/// Returns the type of a numeric literal written without a suffix, which is
+/// the smallest type that holds its value.
+fn number_type(n: &BigDecimal) -> Type {
+    if !n.is_integer() {
+        Type::Decimal
+    } else if n.to_i32().is_some() {
+        Type::Int32
+    } else if n.to_i64().is_some() {
+        Type::Int64
+    } else {
+        Type::Decimal
+    }
+}
@@ -1,6 +1,11 @@
 #![doc = include_str!("../README.md")]
 
+pub mod expand;
 pub mod filters;
+pub mod orderby;
+pub mod pagination;
+pub mod query;
+pub mod select;
 
 // Re-exports
 pub use bigdecimal;
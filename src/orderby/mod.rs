@@ -0,0 +1,42 @@
+pub(crate) mod parse;
+mod to_query_string;
+
+use crate::filters::Expr;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub use parse::parse_str;
+pub use to_query_string::{to_query_string, write_query_string};
+
+/// Represents a single sort item of an `$orderby` option.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OrderByItem {
+    /// The expression to sort by.
+    pub expr: Expr,
+
+    /// The direction to sort in.
+    pub direction: SortDirection,
+}
+
+/// Represents the direction of a sort item.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SortDirection {
+    /// Ascending order, which is the default when no direction is given.
+    #[default]
+    Ascending,
+
+    /// Descending order.
+    Descending,
+}
+
+/// Converts a `SortDirection` to its string representation.
+impl std::fmt::Display for SortDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortDirection::Ascending => write!(f, "asc"),
+            SortDirection::Descending => write!(f, "desc"),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use super::OrderByItem;
+use crate::filters::parse::EmbeddedErrors;
+use crate::filters::ParseError;
+
+/// Parses an OData v4 `$orderby` option string into a list of `OrderByItem`.
+///
+/// Each sort key is parsed with the same expression grammar as `$filter`.
+///
+/// ```
+/// use odata_params::orderby::{parse_str, SortDirection};
+///
+/// let order_by = "name desc, length(title)";
+/// let result = parse_str(order_by).expect("valid order by list");
+///
+/// assert_eq!(result.len(), 2);
+/// assert_eq!(result[0].direction, SortDirection::Descending);
+/// assert_eq!(result[1].direction, SortDirection::Ascending);
+/// ```
+pub fn parse_str(query: impl AsRef<str>) -> Result<Vec<OrderByItem>, ParseError> {
+    let query = query.as_ref();
+    let errors = EmbeddedErrors::default();
+
+    match odata_order_by::parse_str(query, &errors) {
+        Ok(items) => items,
+        Err(error) => Err(errors.syntax_error(query, error)),
+    }
+}
+
+peg::parser! {
+    /// Parses OData v4 `$orderby` options, where sort keys are parsed with
+    /// the expression grammar of `$filter`.
+    pub(crate) grammar odata_order_by(errors: &EmbeddedErrors) for str {
+        use super::{OrderByItem, ParseError};
+        use crate::filters::parse::{Embedded, Spanned};
+        use crate::orderby::SortDirection;
+
+        /// Entry point for parsing an `$orderby` option string.
+        pub(crate) rule parse_str() -> Result<Vec<OrderByItem>, ParseError>
+            = order_by()
+
+        /// Entry point for parsing an `$orderby` option that starts at the
+        /// offset `start` within another option string, storing it in
+        /// `embedded` along with the offset just past it.
+        pub(crate) rule nested(
+            start: usize,
+            embedded: &Embedded<Result<Vec<OrderByItem>, ParseError>>,
+        )
+            = skip_to(start) l:order_by() end:position!() quiet!{
+                {? embedded.replace(Some((l, end))); Err::<(), _>("") }
+            }
+
+        /// Parses a list of sort items separated by commas.
+        rule order_by() -> Result<Vec<OrderByItem>, ParseError>
+            = _ l:order_by_item() ++ ( _ "," _ ) _ { l.into_iter().collect() }
+
+        /// Parses a single sort item with an optional direction.
+        rule order_by_item() -> Result<OrderByItem, ParseError>
+            = e:expression() d:(_ d:sort_direction() { d })? {
+                Ok(OrderByItem { expr: e?.0, direction: d.unwrap_or_default() })
+            }
+
+        /// Parses a sort key with the expression grammar of `$filter`.
+        rule expression() -> Spanned
+            = #{|input, pos| errors.expression(input, pos)}
+
+        /// Parses a sort direction.
+        rule sort_direction() -> SortDirection
+            = "asc" !identifier_character() { SortDirection::Ascending }
+            / "desc" !identifier_character() { SortDirection::Descending }
+
+        /// Matches a single character that may continue an identifier.
+        rule identifier_character()
+            = ['a'..='z'|'A'..='Z'|'_'|'0'..='9']
+
+        /// Moves to the given offset without matching anything.
+        rule skip_to(offset: usize)
+            = #{|_, _| peg::RuleResult::Matched(offset, ())}
+
+        /// Matches zero or more whitespace characters.
+        rule _()
+            = quiet!{ [' '|'\t'|'\n'|'\r']* }
+    }
+}
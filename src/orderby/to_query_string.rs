@@ -0,0 +1,53 @@
+use super::{OrderByItem, SortDirection};
+use crate::filters;
+use std::fmt::{self, Write};
+
+/// Converts a list of `OrderByItem` to its corresponding OData `$orderby` string representation.
+///
+/// A `Result` containing the resulting query string or a `fmt::Error`.
+///
+/// ```
+/// use odata_params::filters::Expr;
+/// use odata_params::orderby::{to_query_string, OrderByItem, SortDirection};
+///
+/// let items = vec![
+///     OrderByItem {
+///         expr: Expr::Identifier("name".to_owned()),
+///         direction: SortDirection::Descending,
+///     },
+///     OrderByItem {
+///         expr: Expr::Identifier("age".to_owned()),
+///         direction: SortDirection::Ascending,
+///     },
+/// ];
+///
+/// let query_string = to_query_string(&items).expect("valid order by list");
+///
+/// assert_eq!(query_string, "name desc, age");
+/// ```
+pub fn to_query_string(items: &[OrderByItem]) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+    write_query_string(&mut output, items)?;
+    Ok(output)
+}
+
+/// Writes a list of `OrderByItem` to a writer as its corresponding OData `$orderby` string representation.
+///
+/// Ascending items are written without a direction since it is the default.
+///
+/// A `fmt::Result` indicating the success or failure of the write operation.
+pub fn write_query_string<W: Write>(writer: &mut W, items: &[OrderByItem]) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            write!(writer, ", ")?;
+        }
+
+        filters::write_query_string(writer, &item.expr)?;
+
+        if item.direction == SortDirection::Descending {
+            write!(writer, " {}", item.direction)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+mod parse;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub use parse::{parse_count, parse_skip, parse_top};
+
+/// Represents various errors that can occur while parsing or
+/// applying pagination options.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum PaginationError {
+    /// The `$top` value is not a non-negative integer.
+    #[error("The `$top` value '{value}' is not a non-negative integer.")]
+    InvalidTop { value: String },
+
+    /// The `$top` value is negative.
+    #[error("The `$top` value '{value}' must not be negative.")]
+    NegativeTop { value: String },
+
+    /// The `$skip` value is not a non-negative integer.
+    #[error("The `$skip` value '{value}' is not a non-negative integer.")]
+    InvalidSkip { value: String },
+
+    /// The `$skip` value is negative.
+    #[error("The `$skip` value '{value}' must not be negative.")]
+    NegativeSkip { value: String },
+
+    /// The `$count` value is not a boolean.
+    #[error("The `$count` value '{value}' is not `true` or `false`.")]
+    InvalidCount { value: String },
+
+    /// The `$top` value is larger than the server allows.
+    #[error("The `$top` value {given} exceeds the maximum page size of {max}.")]
+    TopExceedsMaxPageSize { given: u64, max: u64 },
+}
+
+/// Represents the parsed `$top`, `$skip` and `$count` options.
+///
+/// ```
+/// use odata_params::pagination::Pagination;
+///
+/// let pagination = Pagination::parse(Some("10"), Some("20"), Some("true"))
+///     .expect("valid pagination");
+///
+/// assert_eq!(pagination.top, Some(10));
+/// assert_eq!(pagination.skip, Some(20));
+/// assert!(pagination.count);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pagination {
+    /// The maximum number of items requested.
+    pub top: Option<u64>,
+
+    /// The number of items to skip.
+    pub skip: Option<u64>,
+
+    /// Whether the total count of items was requested.
+    pub count: bool,
+}
+
+impl Pagination {
+    /// Parses the raw `$top`, `$skip` and `$count` option values.
+    ///
+    /// Options that are not present in the request should be given as `None`.
+    pub fn parse(
+        top: Option<&str>,
+        skip: Option<&str>,
+        count: Option<&str>,
+    ) -> Result<Self, PaginationError> {
+        Ok(Self {
+            top: top.map(parse_top).transpose()?,
+            skip: skip.map(parse_skip).transpose()?,
+            count: count.map(parse_count).transpose()?.unwrap_or(false),
+        })
+    }
+}
+
+/// Represents a server-side policy for page sizes.
+///
+/// ```
+/// use odata_params::pagination::{PageSizePolicy, Pagination, PaginationError};
+///
+/// let policy = PageSizePolicy {
+///     default_page_size: Some(25),
+///     max_page_size: Some(100),
+/// };
+///
+/// let pagination = policy.apply(Pagination::default()).expect("valid pagination");
+/// assert_eq!(pagination.top, Some(25));
+///
+/// let pagination = Pagination::parse(Some("500"), None, None).expect("valid pagination");
+/// assert_eq!(
+///     policy.apply(pagination),
+///     Err(PaginationError::TopExceedsMaxPageSize { given: 500, max: 100 })
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PageSizePolicy {
+    /// The page size used when the request does not specify `$top`.
+    pub default_page_size: Option<u64>,
+
+    /// The largest `$top` value a request is allowed to specify.
+    pub max_page_size: Option<u64>,
+}
+
+impl PageSizePolicy {
+    /// Applies the policy to the parsed pagination options.
+    ///
+    /// A missing `$top` is replaced by the default page size, and a `$top`
+    /// larger than the maximum page size is rejected.
+    pub fn apply(&self, pagination: Pagination) -> Result<Pagination, PaginationError> {
+        match (pagination.top, self.max_page_size) {
+            (Some(given), Some(max)) if given > max => {
+                Err(PaginationError::TopExceedsMaxPageSize { given, max })
+            }
+
+            (Some(_), _) => Ok(pagination),
+
+            (None, _) => Ok(Pagination {
+                top: self.default_page_size,
+                ..pagination
+            }),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use super::PaginationError;
+
+/// Parses an OData v4 `$top` option value into a non-negative integer.
+///
+/// ```
+/// use odata_params::pagination::{parse_top, PaginationError};
+///
+/// assert_eq!(parse_top("10"), Ok(10));
+/// assert_eq!(
+///     parse_top("-1"),
+///     Err(PaginationError::NegativeTop { value: "-1".to_owned() })
+/// );
+/// ```
+pub fn parse_top(value: impl AsRef<str>) -> Result<u64, PaginationError> {
+    let value = value.as_ref().trim();
+
+    parse_non_negative(value).map_err(|is_negative| {
+        let value = value.to_owned();
+
+        if is_negative {
+            PaginationError::NegativeTop { value }
+        } else {
+            PaginationError::InvalidTop { value }
+        }
+    })
+}
+
+/// Parses an OData v4 `$skip` option value into a non-negative integer.
+///
+/// ```
+/// use odata_params::pagination::parse_skip;
+///
+/// assert_eq!(parse_skip("20"), Ok(20));
+/// ```
+pub fn parse_skip(value: impl AsRef<str>) -> Result<u64, PaginationError> {
+    let value = value.as_ref().trim();
+
+    parse_non_negative(value).map_err(|is_negative| {
+        let value = value.to_owned();
+
+        if is_negative {
+            PaginationError::NegativeSkip { value }
+        } else {
+            PaginationError::InvalidSkip { value }
+        }
+    })
+}
+
+/// Parses an OData v4 `$count` option value into a boolean.
+///
+/// ```
+/// use odata_params::pagination::parse_count;
+///
+/// assert_eq!(parse_count("true"), Ok(true));
+/// assert_eq!(parse_count("FALSE"), Ok(false));
+/// ```
+pub fn parse_count(value: impl AsRef<str>) -> Result<bool, PaginationError> {
+    let value = value.as_ref().trim();
+
+    if value.eq_ignore_ascii_case("true") {
+        Ok(true)
+    } else if value.eq_ignore_ascii_case("false") {
+        Ok(false)
+    } else {
+        Err(PaginationError::InvalidCount {
+            value: value.to_owned(),
+        })
+    }
+}
+
+/// Parses a string of digits into an integer.
+///
+/// The error is `true` when the value is a negative integer, which allows
+/// callers to report a more specific error than a general invalid value.
+fn parse_non_negative(value: &str) -> Result<u64, bool> {
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+
+    if is_digits(value) {
+        value.parse().map_err(|_| false)
+    } else {
+        Err(value.strip_prefix('-').is_some_and(is_digits))
+    }
+}
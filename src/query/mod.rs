@@ -0,0 +1,89 @@
+mod parse;
+
+use crate::expand::{ExpandError, ExpandItem};
+use crate::filters::{AliasError, Expr, ParseError};
+use crate::orderby::OrderByItem;
+use crate::pagination::{Pagination, PaginationError};
+use crate::select::SelectItem;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+pub use parse::{parse_str, percent_decode};
+
+/// Represents various errors that can occur while parsing a full query string.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum QueryError {
+    /// A name or value contains an invalid percent-encoded sequence.
+    #[error("Invalid percent-encoding in '{input}'.")]
+    InvalidPercentEncoding { input: String },
+
+    /// A system query option was given more than once.
+    #[error("Duplicate system query option '${name}'.")]
+    DuplicateOption { name: String },
+
+    /// A parameter alias was given more than once.
+    #[error("Duplicate parameter alias '@{name}'.")]
+    DuplicateAlias { name: String },
+
+    /// A `$` prefixed option is not a known system query option.
+    #[error("Unknown system query option '${name}'.")]
+    UnknownSystemOption { name: String },
+
+    /// Error parsing the `$filter` option.
+    #[error("Invalid `$filter` option: {0}")]
+    Filter(ParseError),
+
+    /// Error parsing the `$orderby` option.
+    #[error("Invalid `$orderby` option: {0}")]
+    OrderBy(ParseError),
+
+    /// Error parsing the `$select` option.
+    #[error("Invalid `$select` option: {0}")]
+    Select(ParseError),
+
+    /// Error parsing the `$expand` option.
+    #[error("Invalid `$expand` option: {0}")]
+    Expand(ExpandError),
+
+    /// Error resolving a parameter alias.
+    #[error("Invalid parameter alias: {0}")]
+    Alias(#[from] AliasError),
+
+    /// Error parsing the `$top`, `$skip` or `$count` options.
+    #[error("Invalid pagination option: {0}")]
+    Pagination(#[from] PaginationError),
+}
+
+/// Represents all the options of a parsed OData v4 query string.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QueryOptions {
+    /// The parsed `$filter` option.
+    pub filter: Option<Expr>,
+
+    /// The parsed `$orderby` option.
+    pub order_by: Option<Vec<OrderByItem>>,
+
+    /// The parsed `$select` option.
+    pub select: Option<Vec<SelectItem>>,
+
+    /// The parsed `$expand` option.
+    pub expand: Option<Vec<ExpandItem>>,
+
+    /// The parsed `$top`, `$skip` and `$count` options.
+    pub pagination: Pagination,
+
+    /// System query options that are recognized but not parsed by this
+    /// library, keyed by their lowercase name without the `$` prefix. These
+    /// are only recognized when given with their prefix.
+    pub other_options: HashMap<String, String>,
+
+    /// Parameter alias values keyed by their name without the `@` prefix.
+    pub aliases: HashMap<String, String>,
+
+    /// Custom query options, which are any options that are not
+    /// system query options or parameter aliases, in the order they were given.
+    pub custom: Vec<(String, String)>,
+}
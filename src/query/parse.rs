@@ -0,0 +1,234 @@
+use super::{QueryError, QueryOptions};
+use crate::expand::{ExpandItem, ExpandOptions};
+use crate::filters::AliasError;
+use crate::orderby::OrderByItem;
+use crate::pagination::Pagination;
+use crate::{expand, filters, orderby, select};
+use std::collections::HashMap;
+
+/// System query options that are recognized but left unparsed.
+///
+/// Unlike the parsed options, these are only recognized with their `$`
+/// prefix, since names such as `id`, `format` or `index` are commonly used
+/// by services for their own custom options.
+const OTHER_SYSTEM_OPTIONS: &[&str] = &[
+    "search",
+    "format",
+    "skiptoken",
+    "deltatoken",
+    "apply",
+    "compute",
+    "index",
+    "schemaversion",
+    "levels",
+    "id",
+];
+
+/// Parses a raw OData v4 query string into `QueryOptions`.
+///
+/// The query string may start with a `?`. Names and values are
+/// percent-decoded, system query option names are matched
+/// case-insensitively, and the `$` prefix is optional for the options parsed
+/// here. Other system options such as `$format` need their prefix: they are
+/// only kept as raw values, so treating an unprefixed `format=json` as one
+/// would take a custom option away from the service without parsing
+/// anything in return. A `+` is kept as is rather than being decoded as a
+/// space.
+///
+/// Parameter aliases such as `@p` are collected separately and resolved
+/// within the `$filter`, `$orderby` and `$expand` options.
+///
+/// ```
+/// use odata_params::query::parse_str;
+///
+/// let query = "$filter=name%20eq%20'John'&$orderby=name desc&$top=10&api-version=2";
+/// let options = parse_str(query).expect("valid query options");
+///
+/// assert!(options.filter.is_some());
+/// assert_eq!(options.order_by.map(|items| items.len()), Some(1));
+/// assert_eq!(options.pagination.top, Some(10));
+/// assert_eq!(options.custom, vec![("api-version".to_owned(), "2".to_owned())]);
+/// ```
+pub fn parse_str(query: impl AsRef<str>) -> Result<QueryOptions, QueryError> {
+    let query = query.as_ref().trim();
+    let query = query.strip_prefix('?').unwrap_or(query);
+
+    let mut system = HashMap::new();
+    let mut aliases = HashMap::new();
+    let mut custom = Vec::new();
+
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let name = percent_decode(name)?;
+        let value = percent_decode(value)?;
+
+        if let Some(alias) = name.strip_prefix('@') {
+            if aliases.insert(alias.to_owned(), value).is_some() {
+                return Err(QueryError::DuplicateAlias {
+                    name: alias.to_owned(),
+                });
+            }
+
+            continue;
+        }
+
+        let lowercase = name.to_ascii_lowercase();
+        let (is_prefixed, option) = match lowercase.strip_prefix('$') {
+            Some(option) => (true, option),
+            None => (false, lowercase.as_str()),
+        };
+
+        let is_system = if is_prefixed {
+            is_parsed_option(option) || OTHER_SYSTEM_OPTIONS.contains(&option)
+        } else {
+            is_parsed_option(option)
+        };
+
+        if is_system {
+            if system.insert(option.to_owned(), value).is_some() {
+                return Err(QueryError::DuplicateOption {
+                    name: option.to_owned(),
+                });
+            }
+        } else if is_prefixed {
+            return Err(QueryError::UnknownSystemOption {
+                name: option.to_owned(),
+            });
+        } else {
+            custom.push((name, value));
+        }
+    }
+
+    let filter = system
+        .remove("filter")
+        .map(|filter| filters::parse_str(filter).map_err(QueryError::Filter))
+        .transpose()?
+        .map(|filter| filters::resolve_aliases(&filter, &aliases))
+        .transpose()?;
+
+    let order_by = system
+        .remove("orderby")
+        .map(|order_by| orderby::parse_str(order_by).map_err(QueryError::OrderBy))
+        .transpose()?
+        .map(|order_by| resolve_order_by(order_by, &aliases))
+        .transpose()?;
+
+    let select = system
+        .remove("select")
+        .map(|select| select::parse_str(select).map_err(QueryError::Select))
+        .transpose()?;
+
+    let expand = system
+        .remove("expand")
+        .map(|expand| expand::parse_str(expand).map_err(QueryError::Expand))
+        .transpose()?
+        .map(|expand| resolve_expand(expand, &aliases))
+        .transpose()?;
+
+    let pagination = Pagination::parse(
+        system.remove("top").as_deref(),
+        system.remove("skip").as_deref(),
+        system.remove("count").as_deref(),
+    )?;
+
+    Ok(QueryOptions {
+        filter,
+        order_by,
+        select,
+        expand,
+        pagination,
+        other_options: system,
+        aliases,
+        custom,
+    })
+}
+
+/// Resolves the parameter aliases within the sort keys of an `$orderby` option.
+fn resolve_order_by(
+    items: Vec<OrderByItem>,
+    aliases: &HashMap<String, String>,
+) -> Result<Vec<OrderByItem>, AliasError> {
+    items
+        .into_iter()
+        .map(|item| {
+            Ok(OrderByItem {
+                expr: filters::resolve_aliases(&item.expr, aliases)?,
+                ..item
+            })
+        })
+        .collect()
+}
+
+/// Resolves the parameter aliases within the nested options of an `$expand` option.
+fn resolve_expand(
+    items: Vec<ExpandItem>,
+    aliases: &HashMap<String, String>,
+) -> Result<Vec<ExpandItem>, AliasError> {
+    items
+        .into_iter()
+        .map(|item| {
+            let options = item.options;
+
+            Ok(ExpandItem {
+                options: ExpandOptions {
+                    filter: options
+                        .filter
+                        .map(|filter| filters::resolve_aliases(&filter, aliases))
+                        .transpose()?,
+                    order_by: options
+                        .order_by
+                        .map(|order_by| resolve_order_by(order_by, aliases))
+                        .transpose()?,
+                    expand: options
+                        .expand
+                        .map(|expand| resolve_expand(expand, aliases))
+                        .transpose()?,
+                    ..options
+                },
+                ..item
+            })
+        })
+        .collect()
+}
+
+/// Decodes percent-encoded sequences in a query string component.
+///
+/// ```
+/// use odata_params::query::percent_decode;
+///
+/// assert_eq!(percent_decode("name%20eq%20'Jos%C3%A9'"), Ok("name eq 'José'".to_owned()));
+/// ```
+pub fn percent_decode(input: &str) -> Result<String, QueryError> {
+    let error = || QueryError::InvalidPercentEncoding {
+        input: input.to_owned(),
+    };
+
+    let mut bytes = Vec::with_capacity(input.len());
+    let mut iter = input.bytes();
+
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let high = iter.next().and_then(hex_digit).ok_or_else(error)?;
+            let low = iter.next().and_then(hex_digit).ok_or_else(error)?;
+            bytes.push(high << 4 | low);
+        } else {
+            bytes.push(byte);
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|_| error())
+}
+
+/// Converts an ASCII hexadecimal digit to its value.
+fn hex_digit(byte: u8) -> Option<u8> {
+    (byte as char).to_digit(16).map(|digit| digit as u8)
+}
+
+/// Checks if a lowercase option name without its `$` prefix is a system
+/// query option parsed into `QueryOptions`.
+fn is_parsed_option(option: &str) -> bool {
+    matches!(
+        option,
+        "filter" | "orderby" | "select" | "expand" | "top" | "skip" | "count"
+    )
+}
@@ -0,0 +1,24 @@
+pub(crate) mod parse;
+mod to_query_string;
+mod validate;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub use parse::parse_str;
+pub use to_query_string::{to_query_string, write_query_string};
+pub use validate::validate;
+
+/// Represents a single item of a `$select` option.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SelectItem {
+    /// All structural properties, written as `*`.
+    Wildcard,
+
+    /// All operations within a namespace, written as `Namespace.*`.
+    NamespaceWildcard(String),
+
+    /// A property path made of one or more segments, such as `Address/City`.
+    Path(Vec<String>),
+}
@@ -0,0 +1,73 @@
+use super::SelectItem;
+use crate::filters::parse::syntax_error;
+use crate::filters::ParseError;
+
+/// Parses an OData v4 `$select` option string into a list of `SelectItem`.
+///
+/// ```
+/// use odata_params::select::{parse_str, SelectItem};
+///
+/// let select = "Name,Address/City,*,Namespace.*";
+/// let result = parse_str(select).expect("valid select list");
+///
+/// assert_eq!(
+///     result,
+///     vec![
+///         SelectItem::Path(vec!["Name".to_owned()]),
+///         SelectItem::Path(vec!["Address".to_owned(), "City".to_owned()]),
+///         SelectItem::Wildcard,
+///         SelectItem::NamespaceWildcard("Namespace".to_owned()),
+///     ]
+/// );
+/// ```
+pub fn parse_str(query: impl AsRef<str>) -> Result<Vec<SelectItem>, ParseError> {
+    let query = query.as_ref();
+
+    match odata_select::parse_str(query) {
+        Ok(items) => Ok(items),
+        Err(error) => Err(syntax_error(query, error)),
+    }
+}
+
+peg::parser! {
+    /// Parses OData v4 `$select` options.
+    pub(crate) grammar odata_select() for str {
+        use super::SelectItem;
+        use crate::filters::parse::Embedded;
+
+        /// Entry point for parsing a `$select` option string.
+        pub(crate) rule parse_str() -> Vec<SelectItem>
+            = select()
+
+        /// Entry point for parsing a `$select` option that starts at the
+        /// offset `start` within another option string, storing it in
+        /// `embedded` along with the offset just past it.
+        pub(crate) rule nested(start: usize, embedded: &Embedded<Vec<SelectItem>>)
+            = skip_to(start) l:select() end:position!() quiet!{
+                {? embedded.replace(Some((l, end))); Err::<(), _>("") }
+            }
+
+        /// Parses a list of select items separated by commas.
+        rule select() -> Vec<SelectItem>
+            = _ l:select_item() ++ ( _ "," _ ) _ { l }
+
+        /// Parses a single select item, which can be a wildcard or a property path.
+        rule select_item() -> SelectItem
+            = "*" { SelectItem::Wildcard }
+            / n:$(identifier() ++ ".") ".*" { SelectItem::NamespaceWildcard(n.to_string()) }
+            / p:identifier() ++ "/" { SelectItem::Path(p) }
+
+        /// Parses an identifier.
+        rule identifier() -> String
+            = quiet!{ s:$(['a'..='z'|'A'..='Z'|'_']['a'..='z'|'A'..='Z'|'_'|'0'..='9']*) { s.to_string() } }
+            / expected!("identifier")
+
+        /// Moves to the given offset without matching anything.
+        rule skip_to(offset: usize)
+            = #{|_, _| peg::RuleResult::Matched(offset, ())}
+
+        /// Matches zero or more whitespace characters.
+        rule _()
+            = quiet!{ [' '|'\t'|'\n'|'\r']* }
+    }
+}
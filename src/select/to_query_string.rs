@@ -0,0 +1,43 @@
+use super::SelectItem;
+use std::fmt::{self, Write};
+
+/// Converts a list of `SelectItem` to its corresponding OData `$select` string representation.
+///
+/// A `Result` containing the resulting query string or a `fmt::Error`.
+///
+/// ```
+/// use odata_params::select::{to_query_string, SelectItem};
+///
+/// let items = vec![
+///     SelectItem::Path(vec!["Address".to_owned(), "City".to_owned()]),
+///     SelectItem::Wildcard,
+/// ];
+///
+/// let query_string = to_query_string(&items).expect("valid select list");
+///
+/// assert_eq!(query_string, "Address/City,*");
+/// ```
+pub fn to_query_string(items: &[SelectItem]) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+    write_query_string(&mut output, items)?;
+    Ok(output)
+}
+
+/// Writes a list of `SelectItem` to a writer as its corresponding OData `$select` string representation.
+///
+/// A `fmt::Result` indicating the success or failure of the write operation.
+pub fn write_query_string<W: Write>(writer: &mut W, items: &[SelectItem]) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            write!(writer, ",")?;
+        }
+
+        match item {
+            SelectItem::Wildcard => write!(writer, "*")?,
+            SelectItem::NamespaceWildcard(namespace) => write!(writer, "{namespace}.*")?,
+            SelectItem::Path(segments) => write!(writer, "{}", segments.join("/"))?,
+        }
+    }
+
+    Ok(())
+}
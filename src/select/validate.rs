@@ -0,0 +1,71 @@
+use super::SelectItem;
+use crate::filters::{IdentifiersTypeMap, ValidationError};
+
+impl SelectItem {
+    /// Validates that a selected property path is a known identifier,
+    /// following nested segments through declared complex types.
+    ///
+    /// Wildcards are always valid.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use odata_params::filters::{IdentifiersTypeMap, Type, ValidationError};
+    /// use odata_params::select::SelectItem;
+    ///
+    /// let mut id_map = HashMap::new();
+    /// id_map.insert("Name".to_string(), Type::String);
+    /// let identifiers = IdentifiersTypeMap::from(id_map);
+    ///
+    /// let item = SelectItem::Path(vec!["Name".to_owned()]);
+    /// assert_eq!(item.validate(&identifiers), Ok(()));
+    ///
+    /// let item = SelectItem::Path(vec!["Age".to_owned()]);
+    /// assert_eq!(
+    ///     item.validate(&identifiers),
+    ///     Err(ValidationError::UndefinedIdentifier { name: "Age".to_owned() })
+    /// );
+    /// ```
+    pub fn validate(&self, identifiers: &IdentifiersTypeMap) -> Result<(), ValidationError> {
+        match self {
+            SelectItem::Wildcard | SelectItem::NamespaceWildcard(_) => Ok(()),
+
+            SelectItem::Path(segments) => {
+                let (first, members) =
+                    segments
+                        .split_first()
+                        .ok_or_else(|| ValidationError::UndefinedIdentifier {
+                            name: String::new(),
+                        })?;
+
+                let root_type = identifiers.get(first).cloned().ok_or_else(|| {
+                    ValidationError::UndefinedIdentifier {
+                        name: first.to_owned(),
+                    }
+                })?;
+
+                identifiers.resolve_members(root_type, members).map(|_| ())
+            }
+        }
+    }
+}
+
+/// Validates every item of a `$select` list, stopping at the first unknown property.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use odata_params::filters::{IdentifiersTypeMap, Type};
+/// use odata_params::select::{parse_str, validate};
+///
+/// let mut id_map = HashMap::new();
+/// id_map.insert("Name".to_string(), Type::String);
+/// let identifiers = IdentifiersTypeMap::from(id_map);
+///
+/// let items = parse_str("Name,*").expect("valid select list");
+/// assert_eq!(validate(&items, &identifiers), Ok(()));
+/// ```
+pub fn validate(
+    items: &[SelectItem],
+    identifiers: &IdentifiersTypeMap,
+) -> Result<(), ValidationError> {
+    items.iter().try_for_each(|item| item.validate(identifiers))
+}
@@ -0,0 +1,189 @@
+use odata_params::bigdecimal::BigDecimal;
+use odata_params::expand::{
+    parse_str, ExpandError, ExpandItem, ExpandOptions, ExpandTarget, Levels,
+};
+use odata_params::filters::CompareOperator::*;
+use odata_params::filters::{Expr, ParseError, Span, Value};
+use odata_params::orderby::{OrderByItem, SortDirection};
+use odata_params::pagination::Pagination;
+use odata_params::select::SelectItem;
+
+fn path(segments: &[&str]) -> ExpandTarget {
+    ExpandTarget::Path(segments.iter().map(|s| s.to_string()).collect())
+}
+
+#[test]
+fn simple_expand() {
+    let result = parse_str("Orders, Customer/Address,*").expect("valid expand tree");
+
+    assert_eq!(
+        result,
+        vec![
+            ExpandItem {
+                target: path(&["Orders"]),
+                options: ExpandOptions::default(),
+            },
+            ExpandItem {
+                target: path(&["Customer", "Address"]),
+                options: ExpandOptions::default(),
+            },
+            ExpandItem {
+                target: ExpandTarget::Wildcard,
+                options: ExpandOptions::default(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn nested_options() {
+    let expand = "Orders($filter=Total gt 100;$orderby=Date desc;$top=5;$expand=Items)";
+    let result = parse_str(expand).expect("valid expand tree");
+
+    assert_eq!(
+        result,
+        vec![ExpandItem {
+            target: path(&["Orders"]),
+            options: ExpandOptions {
+                filter: Some(Expr::Compare(
+                    Expr::Identifier("Total".to_owned()).into(),
+                    GreaterThan,
+                    Expr::Value(Value::Number(BigDecimal::from(100))).into()
+                )),
+                order_by: Some(vec![OrderByItem {
+                    expr: Expr::Identifier("Date".to_owned()),
+                    direction: SortDirection::Descending,
+                }]),
+                expand: Some(vec![ExpandItem {
+                    target: path(&["Items"]),
+                    options: ExpandOptions::default(),
+                }]),
+                pagination: Pagination {
+                    top: Some(5),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        }]
+    );
+}
+
+#[test]
+fn deeply_nested_options() {
+    let expand = "Orders($expand=Items($select=Name,Price;$skip=2;$count=true),Payments),Manager($levels=max)";
+    let result = parse_str(expand).expect("valid expand tree");
+
+    assert_eq!(
+        result,
+        vec![
+            ExpandItem {
+                target: path(&["Orders"]),
+                options: ExpandOptions {
+                    expand: Some(vec![
+                        ExpandItem {
+                            target: path(&["Items"]),
+                            options: ExpandOptions {
+                                select: Some(vec![
+                                    SelectItem::Path(vec!["Name".to_owned()]),
+                                    SelectItem::Path(vec!["Price".to_owned()]),
+                                ]),
+                                pagination: Pagination {
+                                    skip: Some(2),
+                                    count: true,
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                        },
+                        ExpandItem {
+                            target: path(&["Payments"]),
+                            options: ExpandOptions::default(),
+                        },
+                    ]),
+                    ..Default::default()
+                },
+            },
+            ExpandItem {
+                target: path(&["Manager"]),
+                options: ExpandOptions {
+                    levels: Some(Levels::Max),
+                    ..Default::default()
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn case_insensitive_option_names() {
+    let result = parse_str("Orders(TOP=3; $Levels=2)").expect("valid expand tree");
+
+    assert_eq!(result[0].options.pagination.top, Some(3));
+    assert_eq!(result[0].options.levels, Some(Levels::Depth(2)));
+}
+
+#[test]
+fn invalid_expand() {
+    assert!(matches!(
+        parse_str(""),
+        Err(ExpandError::Parse(ParseError::Parsing { .. }))
+    ));
+    assert!(matches!(
+        parse_str("Orders()"),
+        Err(ExpandError::Parse(ParseError::Parsing { .. }))
+    ));
+    assert!(matches!(
+        parse_str("Orders($top=-1)"),
+        Err(ExpandError::Parse(ParseError::Parsing { .. }))
+    ));
+    assert_eq!(
+        parse_str("Orders($top=1;$top=2)"),
+        Err(ExpandError::DuplicateOption {
+            span: Span { start: 14, end: 20 }
+        })
+    );
+    assert_eq!(
+        parse_str("Orders($count=false;TOP=1;$count=true)"),
+        Err(ExpandError::DuplicateOption {
+            span: Span { start: 26, end: 37 }
+        })
+    );
+    assert!(matches!(
+        parse_str("Orders($unknown=1)"),
+        Err(ExpandError::Parse(ParseError::Parsing { .. }))
+    ));
+    assert!(matches!(
+        parse_str("Orders($filter=Total gt)"),
+        Err(ExpandError::Parse(ParseError::Parsing { .. }))
+    ));
+}
+
+#[test]
+fn expand_error_messages() {
+    let message = |expand: &str| parse_str(expand).unwrap_err().to_string();
+
+    assert_eq!(
+        message("Orders($unknown=1)"),
+        "Error during general parsing at line 1, column 8, expected a nested query option.\nOrders($unknown=1)\n       ^"
+    );
+    assert_eq!(
+        message("Orders($count=maybe)"),
+        "Error during general parsing at line 1, column 15, expected boolean.\nOrders($count=maybe)\n              ^"
+    );
+    assert_eq!(
+        message("Orders($levels=x)"),
+        "Error during general parsing at line 1, column 16, expected one of integer, max.\nOrders($levels=x)\n               ^"
+    );
+    assert_eq!(
+        message("Orders($filter=Total gt)"),
+        "Error during general parsing at line 1, column 24, expected one of \"(\", \"-\", \"@\", identifier, literal, not.\nOrders($filter=Total gt)\n                       ^"
+    );
+    assert_eq!(
+        message("Orders($select=Name,)"),
+        "Error during general parsing at line 1, column 21, expected one of \"*\", identifier.\nOrders($select=Name,)\n                    ^"
+    );
+    assert_eq!(
+        message("Orders($top=1;$top=2)"),
+        "Duplicate nested query option at 14..20."
+    );
+}
@@ -0,0 +1,52 @@
+use odata_params::bigdecimal::BigDecimal;
+use odata_params::expand::{parse_str, to_query_string, ExpandItem, ExpandOptions, ExpandTarget};
+use odata_params::filters::{CompareOperator, Expr, Value};
+
+#[test]
+fn without_options() {
+    let items = vec![
+        ExpandItem {
+            target: ExpandTarget::Path(vec!["Customer".to_owned(), "Address".to_owned()]),
+            options: ExpandOptions::default(),
+        },
+        ExpandItem {
+            target: ExpandTarget::Wildcard,
+            options: ExpandOptions::default(),
+        },
+    ];
+
+    let result = to_query_string(&items).expect("valid expand tree");
+    assert_eq!(result, "Customer/Address,*");
+}
+
+#[test]
+fn nested_filter() {
+    let items = vec![ExpandItem {
+        target: ExpandTarget::Path(vec!["Orders".to_owned()]),
+        options: ExpandOptions {
+            filter: Some(Expr::Compare(
+                Box::new(Expr::Identifier("Total".to_owned())),
+                CompareOperator::GreaterThan,
+                Box::new(Expr::Value(Value::Number(BigDecimal::from(100)))),
+            )),
+            ..Default::default()
+        },
+    }];
+
+    let result = to_query_string(&items).expect("valid expand tree");
+    assert_eq!(result, "Orders($filter=Total gt 100)");
+}
+
+#[test]
+fn round_trip() {
+    let expand = [
+        "Orders($filter=Total gt 100;$orderby=Date desc;",
+        "$expand=Items($select=Name,Price;$skip=2;$count=true);$top=5)",
+        ",Manager($levels=max)",
+    ]
+    .join("");
+    let items = parse_str(&expand).expect("valid expand tree");
+
+    let result = to_query_string(&items).expect("valid expand tree");
+    assert_eq!(result, expand);
+}
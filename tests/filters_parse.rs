@@ -1,6 +1,7 @@
 use odata_params::bigdecimal::BigDecimal;
+use odata_params::filters::ArithmeticOperator::*;
 use odata_params::filters::CompareOperator::*;
-use odata_params::filters::{parse_str, Expr, Value};
+use odata_params::filters::{parse_str, Expr, LambdaOperator, Value};
 use std::str::FromStr;
 
 #[test]
@@ -161,6 +162,39 @@ fn function_call_endswith() {
     );
 }
 
+#[test]
+fn type_functions() {
+    let filter = "cast(Price, Edm.String) eq '10' and isof(Sales.Customer)";
+    let result = parse_str(filter).expect("valid filter tree");
+
+    assert_eq!(
+        result,
+        Expr::And(
+            Expr::Compare(
+                Expr::Function(
+                    "cast".to_owned(),
+                    vec![
+                        Expr::Identifier("Price".to_owned()),
+                        Expr::TypeName("Edm.String".to_owned())
+                    ]
+                )
+                .into(),
+                Equal,
+                Expr::Value(Value::String("10".to_owned())).into()
+            )
+            .into(),
+            Expr::Function(
+                "isof".to_owned(),
+                vec![Expr::TypeName("Sales.Customer".to_owned())]
+            )
+            .into()
+        )
+    );
+
+    // Type names are only allowed within the type functions.
+    assert!(parse_str("Edm.String eq 'a'").is_err());
+}
+
 #[test]
 fn function_call_complex() {
     let filter = "concat(concat(city, ', '), country) eq 'Berlin, Germany'";
@@ -606,3 +640,371 @@ fn multiple_nested_functions() {
         )
     );
 }
+
+#[test]
+fn arithmetic_comparison() {
+    let filter = "Price mul Quantity gt 100";
+    let result = parse_str(filter).expect("valid filter tree");
+
+    assert_eq!(
+        result,
+        Expr::Compare(
+            Expr::Arithmetic(
+                Expr::Identifier("Price".to_owned()).into(),
+                Mul,
+                Expr::Identifier("Quantity".to_owned()).into()
+            )
+            .into(),
+            GreaterThan,
+            Expr::Value(Value::Number(BigDecimal::from(100))).into()
+        )
+    );
+}
+
+#[test]
+fn arithmetic_precedence() {
+    let filter = "Price add Tax mul Rate sub Discount divby 2 eq Total mod 10";
+    let result = parse_str(filter).expect("valid filter tree");
+
+    assert_eq!(
+        result,
+        Expr::Compare(
+            Expr::Arithmetic(
+                Expr::Arithmetic(
+                    Expr::Identifier("Price".to_owned()).into(),
+                    Add,
+                    Expr::Arithmetic(
+                        Expr::Identifier("Tax".to_owned()).into(),
+                        Mul,
+                        Expr::Identifier("Rate".to_owned()).into()
+                    )
+                    .into()
+                )
+                .into(),
+                Sub,
+                Expr::Arithmetic(
+                    Expr::Identifier("Discount".to_owned()).into(),
+                    DivBy,
+                    Expr::Value(Value::Number(BigDecimal::from(2))).into()
+                )
+                .into()
+            )
+            .into(),
+            Equal,
+            Expr::Arithmetic(
+                Expr::Identifier("Total".to_owned()).into(),
+                Mod,
+                Expr::Value(Value::Number(BigDecimal::from(10))).into()
+            )
+            .into()
+        )
+    );
+}
+
+#[test]
+fn arithmetic_grouping() {
+    let filter = "(Price add Tax) div Count le 5 and Name eq 'x'";
+    let result = parse_str(filter).expect("valid filter tree");
+
+    assert_eq!(
+        result,
+        Expr::And(
+            Expr::Compare(
+                Expr::Arithmetic(
+                    Expr::Arithmetic(
+                        Expr::Identifier("Price".to_owned()).into(),
+                        Add,
+                        Expr::Identifier("Tax".to_owned()).into()
+                    )
+                    .into(),
+                    Div,
+                    Expr::Identifier("Count".to_owned()).into()
+                )
+                .into(),
+                LessOrEqual,
+                Expr::Value(Value::Number(BigDecimal::from(5))).into()
+            )
+            .into(),
+            Expr::Compare(
+                Expr::Identifier("Name".to_owned()).into(),
+                Equal,
+                Expr::Value(Value::String("x".to_owned())).into()
+            )
+            .into()
+        )
+    );
+}
+
+#[test]
+fn arithmetic_keywords_in_identifiers() {
+    let filter = "address eq modifier";
+    let result = parse_str(filter).expect("valid filter tree");
+
+    assert_eq!(
+        result,
+        Expr::Compare(
+            Expr::Identifier("address".to_owned()).into(),
+            Equal,
+            Expr::Identifier("modifier".to_owned()).into()
+        )
+    );
+}
+
+#[test]
+fn unary_minus() {
+    let filter = "-Price gt 5 and -(Cost sub 2) mul -Rate lt 0";
+    let result = parse_str(filter).expect("valid filter tree");
+
+    assert_eq!(
+        result,
+        Expr::And(
+            Expr::Compare(
+                Expr::Negate(Expr::Identifier("Price".to_owned()).into()).into(),
+                GreaterThan,
+                Expr::Value(Value::Number(BigDecimal::from(5))).into()
+            )
+            .into(),
+            Expr::Compare(
+                Expr::Arithmetic(
+                    Expr::Negate(
+                        Expr::Arithmetic(
+                            Expr::Identifier("Cost".to_owned()).into(),
+                            Sub,
+                            Expr::Value(Value::Number(BigDecimal::from(2))).into()
+                        )
+                        .into()
+                    )
+                    .into(),
+                    Mul,
+                    Expr::Negate(Expr::Identifier("Rate".to_owned()).into()).into()
+                )
+                .into(),
+                LessThan,
+                Expr::Value(Value::Number(BigDecimal::from(0))).into()
+            )
+            .into()
+        )
+    );
+}
+
+#[test]
+fn subtracting_negative_number() {
+    let filter = "Total sub -5 eq 10";
+    let result = parse_str(filter).expect("valid filter tree");
+
+    assert_eq!(
+        result,
+        Expr::Compare(
+            Expr::Arithmetic(
+                Expr::Identifier("Total".to_owned()).into(),
+                Sub,
+                Expr::Value(Value::Number(BigDecimal::from(-5))).into()
+            )
+            .into(),
+            Equal,
+            Expr::Value(Value::Number(BigDecimal::from(10))).into()
+        )
+    );
+}
+
+#[test]
+fn has_operator() {
+    let filter = "Style has Sales.Color'Yellow' and not Style has Sales.Color'Red,Blue'";
+    let result = parse_str(filter).expect("valid filter tree");
+
+    assert_eq!(
+        result,
+        Expr::And(
+            Expr::Has(
+                Expr::Identifier("Style".to_owned()).into(),
+                Expr::Value(Value::Enum {
+                    type_name: "Sales.Color".to_owned(),
+                    members: vec!["Yellow".to_owned()],
+                })
+                .into()
+            )
+            .into(),
+            Expr::Not(
+                Expr::Has(
+                    Expr::Identifier("Style".to_owned()).into(),
+                    Expr::Value(Value::Enum {
+                        type_name: "Sales.Color".to_owned(),
+                        members: vec!["Red".to_owned(), "Blue".to_owned()],
+                    })
+                    .into()
+                )
+                .into()
+            )
+            .into()
+        )
+    );
+}
+
+#[test]
+fn has_operator_comparison() {
+    let filter = "Style has Sales.Color'Red' eq true";
+    let result = parse_str(filter).expect("valid filter tree");
+
+    assert_eq!(
+        result,
+        Expr::Compare(
+            Expr::Has(
+                Expr::Identifier("Style".to_owned()).into(),
+                Expr::Value(Value::Enum {
+                    type_name: "Sales.Color".to_owned(),
+                    members: vec!["Red".to_owned()],
+                })
+                .into()
+            )
+            .into(),
+            Equal,
+            Expr::Value(Value::Bool(true)).into()
+        )
+    );
+}
+
+#[test]
+fn lambda_any() {
+    let filter = "Tags/any(t: t eq 'urgent') and Orders/any()";
+    let result = parse_str(filter).expect("valid filter tree");
+
+    assert_eq!(
+        result,
+        Expr::And(
+            Expr::Lambda(
+                Expr::Identifier("Tags".to_owned()).into(),
+                LambdaOperator::Any,
+                Some((
+                    "t".to_owned(),
+                    Expr::Compare(
+                        Expr::Identifier("t".to_owned()).into(),
+                        Equal,
+                        Expr::Value(Value::String("urgent".to_owned())).into()
+                    )
+                    .into()
+                ))
+            )
+            .into(),
+            Expr::Lambda(
+                Expr::Identifier("Orders".to_owned()).into(),
+                LambdaOperator::Any,
+                None
+            )
+            .into()
+        )
+    );
+}
+
+#[test]
+fn lambda_all_nested() {
+    let filter = "Groups/all(g:Members/any(m: m eq g))";
+    let result = parse_str(filter).expect("valid filter tree");
+
+    assert_eq!(
+        result,
+        Expr::Lambda(
+            Expr::Identifier("Groups".to_owned()).into(),
+            LambdaOperator::All,
+            Some((
+                "g".to_owned(),
+                Expr::Lambda(
+                    Expr::Identifier("Members".to_owned()).into(),
+                    LambdaOperator::Any,
+                    Some((
+                        "m".to_owned(),
+                        Expr::Compare(
+                            Expr::Identifier("m".to_owned()).into(),
+                            Equal,
+                            Expr::Identifier("g".to_owned()).into()
+                        )
+                        .into()
+                    ))
+                )
+                .into()
+            ))
+        )
+    );
+}
+
+#[test]
+fn lambda_all_requires_predicate() {
+    assert!(parse_str("Tags/all()").is_err());
+}
+
+#[test]
+fn parameter_alias() {
+    let filter = "Price gt @minPrice and contains(Name, @term)";
+    let result = parse_str(filter).expect("valid filter tree");
+
+    assert_eq!(
+        result,
+        Expr::And(
+            Expr::Compare(
+                Expr::Identifier("Price".to_owned()).into(),
+                GreaterThan,
+                Expr::Alias("minPrice".to_owned()).into()
+            )
+            .into(),
+            Expr::Function(
+                "contains".to_owned(),
+                vec![
+                    Expr::Identifier("Name".to_owned()),
+                    Expr::Alias("term".to_owned())
+                ]
+            )
+            .into()
+        )
+    );
+}
+
+#[test]
+fn member_paths() {
+    let filter = "Address/City eq 'Paris' and Customer/Country/Code eq 'CA'";
+    let result = parse_str(filter).expect("valid filter tree");
+
+    assert_eq!(
+        result,
+        Expr::And(
+            Expr::Compare(
+                Expr::Path(vec!["Address".to_owned(), "City".to_owned()]).into(),
+                Equal,
+                Expr::Value(Value::String("Paris".to_owned())).into()
+            )
+            .into(),
+            Expr::Compare(
+                Expr::Path(vec![
+                    "Customer".to_owned(),
+                    "Country".to_owned(),
+                    "Code".to_owned()
+                ])
+                .into(),
+                Equal,
+                Expr::Value(Value::String("CA".to_owned())).into()
+            )
+            .into()
+        )
+    );
+}
+
+#[test]
+fn lambda_over_member_paths() {
+    let filter = "Customer/Orders/all(o: o/Total gt 10)";
+    let result = parse_str(filter).expect("valid filter tree");
+
+    assert_eq!(
+        result,
+        Expr::Lambda(
+            Expr::Path(vec!["Customer".to_owned(), "Orders".to_owned()]).into(),
+            LambdaOperator::All,
+            Some((
+                "o".to_owned(),
+                Expr::Compare(
+                    Expr::Path(vec!["o".to_owned(), "Total".to_owned()]).into(),
+                    GreaterThan,
+                    Expr::Value(Value::Number(BigDecimal::from(10))).into()
+                )
+                .into()
+            ))
+        )
+    );
+}
@@ -0,0 +1,109 @@
+use odata_params::filters::{parse_str, ParseError, Span};
+
+#[test]
+fn error_location() {
+    let Err(ParseError::Parsing {
+        offset,
+        line,
+        column,
+        snippet,
+        expected,
+    }) = parse_str("name eq 'John' an")
+    else {
+        panic!("expected a parsing error");
+    };
+
+    assert_eq!((offset, line, column), (15, 1, 16));
+    assert_eq!(snippet, "name eq 'John' an");
+    assert!(expected.contains(&"and".to_owned()));
+    assert!(expected.contains(&"or".to_owned()));
+    assert!(expected.contains(&"EOF".to_owned()));
+}
+
+#[test]
+fn error_location_across_lines() {
+    let Err(ParseError::Parsing {
+        offset,
+        line,
+        column,
+        snippet,
+        ..
+    }) = parse_str("  name eq 'John'\r\n  and age gt")
+    else {
+        panic!("expected a parsing error");
+    };
+
+    assert_eq!((offset, line, column), (30, 2, 13));
+    assert_eq!(snippet, "  and age gt");
+}
+
+#[test]
+fn error_expected_tokens() {
+    assert_eq!(
+        parse_str("name eq"),
+        Err(ParseError::Parsing {
+            offset: 7,
+            line: 1,
+            column: 8,
+            snippet: "name eq".to_owned(),
+            expected: vec![
+                r#""(""#.to_owned(),
+                r#""-""#.to_owned(),
+                r#""@""#.to_owned(),
+                "identifier".to_owned(),
+                "literal".to_owned(),
+                "not".to_owned(),
+            ],
+        })
+    );
+}
+
+#[test]
+fn error_display() {
+    let error = parse_str("Tags/any(t t eq 'urgent')").unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        [
+            r#"Error during general parsing at line 1, column 12, expected ":"."#,
+            "Tags/any(t t eq 'urgent')",
+            "           ^",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn literal_error_spans() {
+    assert_eq!(
+        parse_str("d eq 2024-13-01"),
+        Err(ParseError::ParsingDate {
+            span: Span { start: 5, end: 15 }
+        })
+    );
+    assert_eq!(
+        parse_str("t eq 25:00"),
+        Err(ParseError::ParsingTime {
+            span: Span { start: 5, end: 10 }
+        })
+    );
+    assert_eq!(
+        parse_str("at eq 2024-06-24T12:34:56Mars/Base"),
+        Err(ParseError::ParsingTimeZoneNamed {
+            span: Span { start: 25, end: 34 }
+        })
+    );
+    assert_eq!(
+        parse_str(r"name eq 'x\u110000'"),
+        Err(ParseError::ParsingUnicodeCodePoint {
+            span: Span { start: 10, end: 18 }
+        })
+    );
+    assert_eq!(
+        ParseError::ParsingDate {
+            span: Span { start: 5, end: 15 }
+        }
+        .to_string(),
+        "Error parsing a date at 5..15."
+    );
+}
@@ -1,6 +1,10 @@
 use odata_params::bigdecimal::BigDecimal;
+use odata_params::chrono::TimeDelta;
+use odata_params::chrono_tz::{America::Toronto, EST};
 use odata_params::filters::CompareOperator::{self, *};
-use odata_params::filters::{parse_str, Expr, Value};
+use odata_params::filters::{
+    parse_str, ArithmeticOperator, Expr, ParseError, Position, Shape, Span, Spatial, Value,
+};
 use std::str::FromStr;
 
 #[test]
@@ -20,7 +24,7 @@ fn null_value() {
 
 #[test]
 fn boolean_value() {
-    let filter = "isActive eq false and not isBlocked eq true";
+    let filter = "isActive eq false and not (isBlocked eq true)";
     let result = parse_str(filter).expect("valid filter tree");
 
     assert_eq!(
@@ -106,6 +110,78 @@ fn number_value() {
     );
 }
 
+#[test]
+fn negative_number_value() {
+    let filter = "Balance lt -100 and Rate in (-0.5, -12)";
+    let result = parse_str(filter).expect("valid filter tree");
+
+    assert_eq!(
+        result,
+        Expr::And(
+            Expr::Compare(
+                Expr::Identifier("Balance".to_owned()).into(),
+                LessThan,
+                Expr::Value(Value::Number((-100).into())).into()
+            )
+            .into(),
+            Expr::In(
+                Expr::Identifier("Rate".to_owned()).into(),
+                vec![
+                    Expr::Value(Value::Number(BigDecimal::from_str("-0.5").unwrap())),
+                    Expr::Value(Value::Number((-12).into())),
+                ]
+            )
+            .into()
+        )
+    );
+}
+
+#[test]
+fn typed_number_value() {
+    let value = |filter: &str| match parse_str(filter).expect("valid filter tree") {
+        Expr::Compare(_, _, rhs) => *rhs,
+        expr => panic!("expected a comparison, got {expr:?}"),
+    };
+
+    assert_eq!(value("Count eq 42L"), Expr::Value(Value::Int64(42)));
+    assert_eq!(
+        value("Price eq 4.5m"),
+        Expr::Value(Value::Decimal(BigDecimal::from_str("4.5").unwrap()))
+    );
+    assert_eq!(
+        value("Distance eq 1.5e3"),
+        Expr::Value(Value::Double(1500.into()))
+    );
+    assert_eq!(
+        value("Distance eq -2E-2"),
+        Expr::Value(Value::Double(BigDecimal::from_str("-0.02").unwrap()))
+    );
+    assert_eq!(
+        value("Distance eq 4.5d"),
+        Expr::Value(Value::Double(BigDecimal::from_str("4.5").unwrap()))
+    );
+    assert_eq!(
+        value("Ratio eq 0.25f"),
+        Expr::Value(Value::Single(BigDecimal::from_str("0.25").unwrap()))
+    );
+    assert_eq!(value("Distance lt INF"), Expr::Value(Value::Infinity));
+    assert_eq!(
+        value("Distance gt -INF"),
+        Expr::Value(Value::NegativeInfinity)
+    );
+    assert_eq!(value("Distance ne NaN"), Expr::Value(Value::NaN));
+
+    // Special values do not swallow identifiers starting with their names.
+    assert_eq!(value("Level eq INFO"), Expr::Identifier("INFO".to_owned()));
+
+    assert_eq!(
+        parse_str("Count eq 4.5L"),
+        Err(ParseError::ParsingNumber {
+            span: Span { start: 9, end: 13 }
+        })
+    );
+}
+
 #[test]
 fn date_value() {
     let filter = "birthdate eq 2024-06-24";
@@ -167,31 +243,318 @@ fn datetime_value() {
     assert_eq!(
         result,
         Expr::Or(
-            Expr::Compare(
-                Expr::Identifier("AT".to_owned()).into(),
-                CompareOperator::Equal,
-                Expr::Value(Value::DateTime("2024-06-24T12:34:56Z".parse().unwrap())).into()
-            )
-            .into(),
             Expr::Or(
                 Expr::Compare(
                     Expr::Identifier("AT".to_owned()).into(),
-                    CompareOperator::GreaterThan,
-                    Expr::Value(Value::DateTime("2024-06-24T10:34:56Z".parse().unwrap())).into()
+                    CompareOperator::Equal,
+                    Expr::Value(Value::DateTime(
+                        "2024-06-24T12:34:56Z".parse().unwrap(),
+                        None
+                    ))
+                    .into()
                 )
                 .into(),
                 Expr::Compare(
                     Expr::Identifier("AT".to_owned()).into(),
-                    CompareOperator::LessThan,
-                    Expr::Value(Value::DateTime("2024-06-24T17:34:56Z".parse().unwrap())).into()
+                    CompareOperator::GreaterThan,
+                    Expr::Value(Value::DateTime(
+                        "2024-06-24T12:34:56+02:00".parse().unwrap(),
+                        None
+                    ))
+                    .into()
                 )
                 .into()
             )
+            .into(),
+            Expr::Compare(
+                Expr::Identifier("AT".to_owned()).into(),
+                CompareOperator::LessThan,
+                Expr::Value(Value::DateTime(
+                    "2024-06-24T12:34:56-05:00".parse().unwrap(),
+                    Some(EST)
+                ))
+                .into()
+            )
             .into()
         )
     );
 }
 
+#[test]
+fn datetime_keeps_offset() {
+    let value = |filter: &str| match parse_str(filter).expect("valid filter tree") {
+        Expr::Compare(_, _, rhs) => match *rhs {
+            Expr::Value(Value::DateTime(dt, zone)) => (dt, zone),
+            expr => panic!("expected a datetime, got {expr:?}"),
+        },
+        expr => panic!("expected a comparison, got {expr:?}"),
+    };
+
+    let (dt, zone) = value("AT eq 2024-03-01T10:00:00-05:00");
+    assert_eq!(dt.offset().local_minus_utc(), -5 * 3600);
+    assert_eq!(dt.to_rfc3339(), "2024-03-01T10:00:00-05:00");
+    assert_eq!(zone, None);
+
+    let (dt, zone) = value("AT eq 2024-03-01T10:00:00America/Toronto");
+    assert_eq!(dt.to_rfc3339(), "2024-03-01T10:00:00-05:00");
+    assert_eq!(zone, Some(Toronto));
+
+    // The offset of a named time zone follows daylight saving time.
+    let (dt, zone) = value("AT eq 2024-07-01T10:00:00America/Toronto");
+    assert_eq!(dt.to_rfc3339(), "2024-07-01T10:00:00-04:00");
+    assert_eq!(zone, Some(Toronto));
+}
+
+#[test]
+fn binary_value() {
+    let value = |filter: &str| match parse_str(filter).expect("valid filter tree") {
+        Expr::Compare(_, _, rhs) => *rhs,
+        expr => panic!("expected a comparison, got {expr:?}"),
+    };
+
+    assert_eq!(
+        value("Data eq binary'T0RhdGE='"),
+        Expr::Value(Value::Binary(b"OData".to_vec()))
+    );
+    assert_eq!(
+        value("Data eq binary'T0RhdGE'"),
+        Expr::Value(Value::Binary(b"OData".to_vec()))
+    );
+    assert_eq!(
+        value("Data eq binary'-_-_'"),
+        Expr::Value(Value::Binary(vec![0xFB, 0xFF, 0xBF]))
+    );
+    assert_eq!(
+        value("Data eq binary''"),
+        Expr::Value(Value::Binary(vec![]))
+    );
+
+    for (filter, span) in [
+        // Standard base64 characters are not part of base64url.
+        ("Data eq binary'T0Rh+GE='", Span { start: 15, end: 23 }),
+        // Unused trailing bits must be zero.
+        ("Data eq binary'T0RhdGF='", Span { start: 15, end: 23 }),
+        ("Data eq binary'T0RhdGE=='", Span { start: 15, end: 24 }),
+        ("Data eq binary'T0RhdGE=A'", Span { start: 15, end: 24 }),
+        ("Data eq binary'T'", Span { start: 15, end: 16 }),
+    ] {
+        assert_eq!(
+            parse_str(filter),
+            Err(ParseError::ParsingBinary { span }),
+            "{filter}"
+        );
+    }
+}
+
+#[test]
+fn spatial_value() {
+    let value = |filter: &str| match parse_str(filter).expect("valid filter tree") {
+        Expr::Compare(_, _, rhs) => *rhs,
+        expr => panic!("expected a comparison, got {expr:?}"),
+    };
+    let position = |x: &str, y: &str| Position {
+        x: BigDecimal::from_str(x).unwrap(),
+        y: BigDecimal::from_str(y).unwrap(),
+    };
+
+    assert_eq!(
+        value("Location eq geography'SRID=4326;POINT(-73.5 45.5)'"),
+        Expr::Value(Value::Geography(Spatial {
+            srid: Some(4326),
+            shape: Shape::Point(position("-73.5", "45.5")),
+        }))
+    );
+    assert_eq!(
+        value("Route eq geometry'LineString(0 0, 1 1.5e1)'"),
+        Expr::Value(Value::Geometry(Spatial {
+            srid: None,
+            shape: Shape::LineString(vec![position("0", "0"), position("1", "15")]),
+        }))
+    );
+
+    let ring = vec![
+        position("0", "0"),
+        position("1", "0"),
+        position("1", "1"),
+        position("0", "0"),
+    ];
+    assert_eq!(
+        value("Area eq geography'POLYGON((0 0,1 0,1 1,0 0))'"),
+        Expr::Value(Value::Geography(Spatial {
+            srid: None,
+            shape: Shape::Polygon(vec![ring.clone()]),
+        }))
+    );
+    assert_eq!(
+        value("Area eq geography'MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((0 0,1 0,1 1,0 0)))'"),
+        Expr::Value(Value::Geography(Spatial {
+            srid: None,
+            shape: Shape::MultiPolygon(vec![vec![ring.clone()], vec![ring]]),
+        }))
+    );
+    assert_eq!(
+        value("Stops eq geography'MULTIPOINT((0 0),(1 1))'"),
+        Expr::Value(Value::Geography(Spatial {
+            srid: None,
+            shape: Shape::MultiPoint(vec![position("0", "0"), position("1", "1")]),
+        }))
+    );
+    assert_eq!(
+        value("Shapes eq geometry'GEOMETRYCOLLECTION(POINT(1 2),MULTILINESTRING((0 0,1 1)))'"),
+        Expr::Value(Value::Geometry(Spatial {
+            srid: None,
+            shape: Shape::Collection(vec![
+                Shape::Point(position("1", "2")),
+                Shape::MultiLineString(vec![vec![position("0", "0"), position("1", "1")]]),
+            ]),
+        }))
+    );
+
+    for (filter, span) in [
+        // Line strings need at least two positions.
+        (
+            "Route eq geometry'LINESTRING(0 0)'",
+            Span { start: 28, end: 33 },
+        ),
+        // Polygon rings must end on their first position.
+        (
+            "Area eq geography'POLYGON((0 0,1 0,1 1,0 1))'",
+            Span { start: 26, end: 43 },
+        ),
+    ] {
+        assert_eq!(
+            parse_str(filter),
+            Err(ParseError::ParsingSpatial { span }),
+            "{filter}"
+        );
+    }
+    assert!(matches!(
+        parse_str("Location eq geography'POINT(1)'"),
+        Err(ParseError::Parsing { .. })
+    ));
+}
+
+#[test]
+fn collection_and_complex_value() {
+    let value = |filter: &str| match parse_str(filter).expect("valid filter tree") {
+        Expr::Compare(_, _, rhs) => *rhs,
+        expr => panic!("expected a comparison, got {expr:?}"),
+    };
+    let number = |n: i32| Value::Number(BigDecimal::from(n));
+
+    assert_eq!(
+        value("Ids eq [1,2, 3]"),
+        Expr::Value(Value::Collection(vec![number(1), number(2), number(3)]))
+    );
+    assert_eq!(
+        value(r#"Tags eq ['a', "b\"c"]"#),
+        Expr::Value(Value::Collection(vec![
+            Value::String("a".to_owned()),
+            Value::String("b\"c".to_owned()),
+        ]))
+    );
+    assert_eq!(value("Tags eq []"), Expr::Value(Value::Collection(vec![])));
+    assert_eq!(
+        value(r#"Address eq {"City":"Montreal", "Zones": [-1, null], "Extra": {}}"#),
+        Expr::Value(Value::Complex(vec![
+            ("City".to_owned(), Value::String("Montreal".to_owned())),
+            (
+                "Zones".to_owned(),
+                Value::Collection(vec![number(-1), Value::Null])
+            ),
+            ("Extra".to_owned(), Value::Complex(vec![])),
+        ]))
+    );
+
+    // A collection literal after `in` is the same as a parenthesized list.
+    assert_eq!(
+        parse_str("Name in ['a','b']"),
+        parse_str("Name in ('a', 'b')")
+    );
+
+    assert!(matches!(
+        parse_str("Tags eq ['a',]"),
+        Err(ParseError::Parsing { .. })
+    ));
+    assert!(matches!(
+        parse_str("Address eq {City:'Montreal'}"),
+        Err(ParseError::Parsing { .. })
+    ));
+}
+
+#[test]
+fn duration_value() {
+    let value = |filter: &str| match parse_str(filter).expect("valid filter tree") {
+        Expr::Compare(_, _, rhs) => *rhs,
+        expr => panic!("expected a comparison, got {expr:?}"),
+    };
+
+    assert_eq!(
+        value("Elapsed lt duration'P1DT2H30M'"),
+        Expr::Value(Value::Duration(
+            TimeDelta::days(1) + TimeDelta::hours(2) + TimeDelta::minutes(30)
+        ))
+    );
+    assert_eq!(
+        value("Elapsed lt duration'-PT1.5S'"),
+        Expr::Value(Value::Duration(-TimeDelta::milliseconds(1500)))
+    );
+    assert_eq!(
+        value("Elapsed gt P7D"),
+        Expr::Value(Value::Duration(TimeDelta::days(7)))
+    );
+    assert_eq!(
+        value("Elapsed gt -PT10M"),
+        Expr::Value(Value::Duration(-TimeDelta::minutes(10)))
+    );
+
+    // Identifiers starting like a duration are still identifiers.
+    assert_eq!(
+        value("Elapsed eq P7Days"),
+        Expr::Identifier("P7Days".to_owned())
+    );
+    assert_eq!(value("Elapsed eq PT"), Expr::Identifier("PT".to_owned()));
+    assert_eq!(
+        value("Elapsed eq -P7Days"),
+        Expr::Negate(Expr::Identifier("P7Days".to_owned()).into())
+    );
+    assert_eq!(
+        value("Elapsed eq -INFO"),
+        Expr::Negate(Expr::Identifier("INFO".to_owned()).into())
+    );
+
+    // Bare durations shadow identifiers of the same name.
+    assert_eq!(
+        parse_str("P1D eq 1"),
+        Ok(Expr::Compare(
+            Expr::Value(Value::Duration(TimeDelta::days(1))).into(),
+            Equal,
+            Expr::Value(Value::Number(BigDecimal::from(1))).into()
+        ))
+    );
+
+    assert_eq!(
+        parse_str("CreatedAt gt now() sub duration'P7D'"),
+        Ok(Expr::Compare(
+            Expr::Identifier("CreatedAt".to_owned()).into(),
+            GreaterThan,
+            Expr::Arithmetic(
+                Expr::Function("now".to_owned(), vec![]).into(),
+                ArithmeticOperator::Sub,
+                Expr::Value(Value::Duration(TimeDelta::days(7))).into()
+            )
+            .into()
+        ))
+    );
+
+    assert_eq!(
+        parse_str("Elapsed lt duration'P999999999999999D'"),
+        Err(ParseError::ParsingDuration {
+            span: Span { start: 20, end: 37 }
+        })
+    );
+}
+
 #[test]
 fn string_value() {
     let filter = "Name in ('Ada', 'Joey')";
@@ -223,3 +586,35 @@ fn escaped_string_comparison() {
         )
     );
 }
+
+#[test]
+fn enum_value() {
+    let filter = "Style eq Sales.Color'Yellow' or Style eq My.Sales.Color'Red,Blue'";
+    let result = parse_str(filter).expect("valid filter tree");
+
+    assert_eq!(
+        result,
+        Expr::Or(
+            Expr::Compare(
+                Expr::Identifier("Style".to_owned()).into(),
+                Equal,
+                Expr::Value(Value::Enum {
+                    type_name: "Sales.Color".to_owned(),
+                    members: vec!["Yellow".to_owned()],
+                })
+                .into()
+            )
+            .into(),
+            Expr::Compare(
+                Expr::Identifier("Style".to_owned()).into(),
+                Equal,
+                Expr::Value(Value::Enum {
+                    type_name: "My.Sales.Color".to_owned(),
+                    members: vec!["Red".to_owned(), "Blue".to_owned()],
+                })
+                .into()
+            )
+            .into()
+        )
+    );
+}
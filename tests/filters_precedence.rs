@@ -0,0 +1,180 @@
+use odata_params::bigdecimal::BigDecimal;
+use odata_params::filters::ArithmeticOperator::*;
+use odata_params::filters::CompareOperator::*;
+use odata_params::filters::{
+    parse_str, to_query_string, ArithmeticOperator, CompareOperator, Expr, Value,
+};
+
+fn id(name: &str) -> Expr {
+    Expr::Identifier(name.to_owned())
+}
+
+fn number(n: i32) -> Expr {
+    Expr::Value(Value::Number(BigDecimal::from(n)))
+}
+
+fn or(lhs: Expr, rhs: Expr) -> Expr {
+    Expr::Or(lhs.into(), rhs.into())
+}
+
+fn and(lhs: Expr, rhs: Expr) -> Expr {
+    Expr::And(lhs.into(), rhs.into())
+}
+
+fn not(inner: Expr) -> Expr {
+    Expr::Not(inner.into())
+}
+
+fn compare(lhs: Expr, op: CompareOperator, rhs: Expr) -> Expr {
+    Expr::Compare(lhs.into(), op, rhs.into())
+}
+
+fn arithmetic(lhs: Expr, op: ArithmeticOperator, rhs: Expr) -> Expr {
+    Expr::Arithmetic(lhs.into(), op, rhs.into())
+}
+
+#[test]
+fn and_binds_tighter_than_or() {
+    assert_eq!(
+        parse_str("a and b or c"),
+        Ok(or(and(id("a"), id("b")), id("c")))
+    );
+    assert_eq!(
+        parse_str("a or b and c"),
+        Ok(or(id("a"), and(id("b"), id("c"))))
+    );
+    assert_eq!(
+        parse_str("a or b and c or d"),
+        Ok(or(or(id("a"), and(id("b"), id("c"))), id("d")))
+    );
+}
+
+#[test]
+fn logical_chains_are_left_associative() {
+    assert_eq!(
+        parse_str("a or b or c"),
+        Ok(or(or(id("a"), id("b")), id("c")))
+    );
+    assert_eq!(
+        parse_str("a and b and c"),
+        Ok(and(and(id("a"), id("b")), id("c")))
+    );
+}
+
+#[test]
+fn comparisons_bind_tighter_than_logical_operators() {
+    assert_eq!(
+        parse_str("a eq 1 and b gt 2 or c in (3, 4)"),
+        Ok(or(
+            and(
+                compare(id("a"), Equal, number(1)),
+                compare(id("b"), GreaterThan, number(2))
+            ),
+            Expr::In(id("c").into(), vec![number(3), number(4)])
+        ))
+    );
+}
+
+#[test]
+fn relational_binds_tighter_than_equality() {
+    assert_eq!(
+        parse_str("a lt b eq c"),
+        Ok(compare(compare(id("a"), LessThan, id("b")), Equal, id("c")))
+    );
+    assert_eq!(
+        parse_str("a eq b lt c"),
+        Ok(compare(id("a"), Equal, compare(id("b"), LessThan, id("c"))))
+    );
+    assert_eq!(
+        parse_str("a eq b ne c"),
+        Ok(compare(compare(id("a"), Equal, id("b")), NotEqual, id("c")))
+    );
+}
+
+#[test]
+fn arithmetic_binds_tighter_than_comparisons() {
+    assert_eq!(
+        parse_str("a add b mul c ge d sub e"),
+        Ok(compare(
+            arithmetic(id("a"), Add, arithmetic(id("b"), Mul, id("c"))),
+            GreaterOrEqual,
+            arithmetic(id("d"), Sub, id("e"))
+        ))
+    );
+    assert_eq!(
+        parse_str("a sub b add c mod d div e"),
+        Ok(arithmetic(
+            arithmetic(id("a"), Sub, id("b")),
+            Add,
+            arithmetic(arithmetic(id("c"), Mod, id("d")), Div, id("e"))
+        ))
+    );
+}
+
+#[test]
+fn not_binds_tighter_than_binary_operators() {
+    assert_eq!(
+        parse_str("not a eq b"),
+        Ok(compare(not(id("a")), Equal, id("b")))
+    );
+    assert_eq!(parse_str("not a and b"), Ok(and(not(id("a")), id("b"))));
+    assert_eq!(
+        parse_str("not (a eq b) or not not c"),
+        Ok(or(not(compare(id("a"), Equal, id("b"))), not(not(id("c")))))
+    );
+}
+
+#[test]
+fn in_binds_as_tightly_as_has() {
+    assert_eq!(
+        parse_str("not a in (1, 2)"),
+        Ok(not(Expr::In(id("a").into(), vec![number(1), number(2)])))
+    );
+    assert_eq!(
+        parse_str("a add b in (1, 2)"),
+        Ok(arithmetic(
+            id("a"),
+            Add,
+            Expr::In(id("b").into(), vec![number(1), number(2)])
+        ))
+    );
+    assert_eq!(
+        parse_str("-a in (1, 2)"),
+        Ok(Expr::Negate(
+            Expr::In(id("a").into(), vec![number(1), number(2)]).into()
+        ))
+    );
+}
+
+#[test]
+fn operator_keywords_within_identifiers() {
+    assert_eq!(
+        parse_str("notes eq order and android ne inbox"),
+        Ok(and(
+            compare(id("notes"), Equal, id("order")),
+            compare(id("android"), NotEqual, id("inbox"))
+        ))
+    );
+}
+
+#[test]
+fn round_trip_preserves_grouping() {
+    for (filter, expected) in [
+        ("a and b or c", "(a and b) or c"),
+        ("a or b and c", "a or (b and c)"),
+        ("(a or b) and c", "(a or b) and c"),
+        ("not (a eq b)", "not (a eq b)"),
+        ("not a eq b", "not a eq b"),
+        ("a eq (b eq c)", "a eq (b eq c)"),
+        ("(a eq b) eq c", "a eq b eq c"),
+        ("a lt b eq c", "a lt b eq c"),
+        ("(a add b) in (1, 2)", "(a add b) in (1, 2)"),
+        ("a add b in (1, 2)", "a add b in (1, 2)"),
+        ("not (a in (1, 2))", "not a in (1, 2)"),
+        ("(not a) in (1, 2)", "(not a) in (1, 2)"),
+    ] {
+        let expr = parse_str(filter).expect("valid filter tree");
+        assert_eq!(to_query_string(&expr).unwrap(), expected);
+        assert_eq!(parse_str(expected), Ok(expr));
+    }
+}
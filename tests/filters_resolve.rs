@@ -0,0 +1,149 @@
+use odata_params::bigdecimal::BigDecimal;
+use odata_params::filters::CompareOperator::*;
+use odata_params::filters::{parse_str, resolve_aliases, AliasError, Expr, ParseError, Value};
+use std::collections::HashMap;
+
+fn aliases(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn literal_alias() {
+    let expr = parse_str("Price gt @minPrice").expect("valid filter tree");
+    let result = resolve_aliases(&expr, &aliases(&[("minPrice", "10")]));
+
+    assert_eq!(
+        result,
+        Ok(Expr::Compare(
+            Expr::Identifier("Price".to_owned()).into(),
+            GreaterThan,
+            Expr::Value(Value::Number(BigDecimal::from(10))).into()
+        ))
+    );
+}
+
+#[test]
+fn expression_alias() {
+    let expr = parse_str("contains(Name, @term) and @isActive").expect("valid filter tree");
+    let result = resolve_aliases(
+        &expr,
+        &aliases(&[("term", "'shoe'"), ("isActive", "Status eq 'active'")]),
+    );
+
+    assert_eq!(
+        result,
+        Ok(parse_str("contains(Name, 'shoe') and Status eq 'active'").expect("valid filter tree"))
+    );
+}
+
+#[test]
+fn json_string_alias() {
+    let expr = parse_str("Name eq @name").expect("valid filter tree");
+    let result = resolve_aliases(&expr, &aliases(&[("name", r#""O'Brien \"Jr\" é😀""#)]));
+
+    assert_eq!(
+        result,
+        Ok(Expr::Compare(
+            Expr::Identifier("Name".to_owned()).into(),
+            Equal,
+            Expr::Value(Value::String("O'Brien \"Jr\" é😀".to_owned())).into()
+        ))
+    );
+}
+
+#[test]
+fn in_list_alias() {
+    let expr = parse_str("Name in @names").expect("valid filter tree");
+    assert_eq!(
+        expr,
+        Expr::InCollection(
+            Expr::Identifier("Name".to_owned()).into(),
+            Expr::Alias("names".to_owned()).into()
+        )
+    );
+
+    let result = resolve_aliases(&expr, &aliases(&[("names", r#"['a',"b"]"#)]));
+    assert_eq!(
+        result,
+        Ok(parse_str("Name in ('a', 'b')").expect("valid filter tree"))
+    );
+
+    // Other values are kept as the collection, which must be one to be valid.
+    let result = resolve_aliases(&expr, &aliases(&[("names", "Tags")]));
+    assert_eq!(
+        result,
+        Ok(parse_str("Name in Tags").expect("valid filter tree"))
+    );
+
+    // Lists holding an alias are not expanded.
+    let expr = parse_str("Name in (@names)").expect("valid filter tree");
+    let result = resolve_aliases(&expr, &aliases(&[("names", "['a']")]));
+    assert_eq!(
+        result,
+        Ok(Expr::In(
+            Expr::Identifier("Name".to_owned()).into(),
+            vec![Expr::Value(Value::Collection(vec![Value::String(
+                "a".to_owned()
+            )]))]
+        ))
+    );
+}
+
+#[test]
+fn nested_aliases() {
+    let expr = parse_str("Price gt @a and Cost lt @a").expect("valid filter tree");
+    let result = resolve_aliases(&expr, &aliases(&[("a", "@b add 1"), ("b", "5")]));
+
+    assert_eq!(
+        result,
+        Ok(parse_str("Price gt 5 add 1 and Cost lt 5 add 1").expect("valid filter tree"))
+    );
+}
+
+#[test]
+fn undefined_alias() {
+    let expr = parse_str("Price gt @minPrice").expect("valid filter tree");
+
+    assert_eq!(
+        resolve_aliases(&expr, &aliases(&[])),
+        Err(AliasError::UndefinedAlias {
+            name: "minPrice".to_owned()
+        })
+    );
+}
+
+#[test]
+fn cyclic_alias() {
+    let expr = parse_str("Price gt @a").expect("valid filter tree");
+
+    assert_eq!(
+        resolve_aliases(&expr, &aliases(&[("a", "@b"), ("b", "@a mul 2")])),
+        Err(AliasError::CyclicAlias {
+            name: "a".to_owned()
+        })
+    );
+    assert_eq!(
+        resolve_aliases(&expr, &aliases(&[("a", "@a")])),
+        Err(AliasError::CyclicAlias {
+            name: "a".to_owned()
+        })
+    );
+}
+
+#[test]
+fn invalid_alias_value() {
+    let expr = parse_str("Price gt @a").expect("valid filter tree");
+
+    let result = resolve_aliases(&expr, &aliases(&[("a", "10 gt")]));
+
+    assert!(matches!(
+        result,
+        Err(AliasError::InvalidAliasValue {
+            name,
+            error: ParseError::Parsing { offset: 5, .. },
+        }) if name == "a"
+    ));
+}
@@ -0,0 +1,137 @@
+use odata_params::filters::{
+    parse_str, parse_str_with_spans, ExprSpans, FunctionsTypeMap, IdentifiersTypeMap, Span, Type,
+    ValidationError,
+};
+use std::collections::HashMap;
+
+/// Returns the source text covered by a span.
+fn text(source: &str, span: Span) -> &str {
+    &source[span.start..span.end]
+}
+
+/// Returns the source text of the node at the given path and of its operator.
+fn node<'a>(source: &'a str, spans: &ExprSpans, path: &[usize]) -> (&'a str, Option<&'a str>) {
+    let node = spans.get(path).expect("node at path");
+    (
+        text(source, node.span),
+        node.operator.map(|operator| text(source, operator)),
+    )
+}
+
+#[test]
+fn binary_and_unary_operators() {
+    let filter = " not (Price mul 2 gt 10) and -Cost le 5 ";
+    let (expr, spans) = parse_str_with_spans(filter).expect("valid filter tree");
+
+    assert_eq!(Ok(expr), parse_str(filter));
+    assert_eq!(
+        node(filter, &spans, &[]),
+        ("not (Price mul 2 gt 10) and -Cost le 5", Some("and"))
+    );
+    assert_eq!(
+        node(filter, &spans, &[0]),
+        ("not (Price mul 2 gt 10)", Some("not"))
+    );
+    assert_eq!(
+        node(filter, &spans, &[0, 0]),
+        ("(Price mul 2 gt 10)", Some("gt"))
+    );
+    assert_eq!(
+        node(filter, &spans, &[0, 0, 0]),
+        ("Price mul 2", Some("mul"))
+    );
+    assert_eq!(node(filter, &spans, &[0, 0, 0, 1]), ("2", None));
+    assert_eq!(node(filter, &spans, &[1, 0]), ("-Cost", Some("-")));
+    assert_eq!(node(filter, &spans, &[1, 0, 0]), ("Cost", None));
+    assert_eq!(node(filter, &spans, &[1, 1]), ("5", None));
+    assert_eq!(spans.get(&[1, 2]), None);
+}
+
+#[test]
+fn lists_lambdas_and_functions() {
+    let filter = "Code in (1, @two) or Customer/Tags/any(t: contains(t, 'vip'))";
+    let (_expr, spans) = parse_str_with_spans(filter).expect("valid filter tree");
+
+    assert_eq!(
+        node(filter, &spans, &[0]),
+        ("Code in (1, @two)", Some("in"))
+    );
+    assert_eq!(node(filter, &spans, &[0, 0]), ("Code", None));
+    assert_eq!(node(filter, &spans, &[0, 2]), ("@two", None));
+    assert_eq!(
+        node(filter, &spans, &[1]),
+        ("Customer/Tags/any(t: contains(t, 'vip'))", Some("any"))
+    );
+    assert_eq!(node(filter, &spans, &[1, 0]), ("Customer/Tags", None));
+    assert_eq!(
+        node(filter, &spans, &[1, 1]),
+        ("contains(t, 'vip')", Some("contains"))
+    );
+    assert_eq!(node(filter, &spans, &[1, 1, 1]), ("'vip'", None));
+}
+
+#[test]
+fn validation_error_locations() {
+    let mut id_map = HashMap::new();
+    id_map.insert("Name".to_string(), Type::String);
+    id_map.insert("Age".to_string(), Type::Int32);
+    id_map.insert("Tags".to_string(), Type::Collection(Box::new(Type::String)));
+    let identifiers = IdentifiersTypeMap::from(id_map);
+
+    let mut func_map = HashMap::new();
+    func_map.insert(
+        "startswith".to_string(),
+        (vec![Type::String, Type::String], None, Type::Boolean),
+    );
+    let functions = FunctionsTypeMap::from(func_map);
+
+    let located = |filter: &str| {
+        let (expr, spans) = parse_str_with_spans(filter).expect("valid filter tree");
+        let error = expr
+            .validate_with_spans(&spans, &identifiers, &functions)
+            .unwrap_err();
+        (text(filter, error.span).to_owned(), error.error)
+    };
+
+    assert_eq!(
+        located("Age gt 1 and Tags/any(t: t eq Label)"),
+        (
+            "Label".to_owned(),
+            ValidationError::UndefinedIdentifier {
+                name: "Label".to_owned()
+            }
+        )
+    );
+    assert_eq!(
+        located("Age gt 1 or startswith(Name, Age)"),
+        (
+            "Age".to_owned(),
+            ValidationError::IncorrectFunctionArgumentType {
+                name: "startswith".to_owned(),
+                position: 2,
+                expected: Type::String,
+                given: Type::Int32,
+            }
+        )
+    );
+    assert_eq!(
+        located("Name in ('a', 2)"),
+        (
+            "2".to_owned(),
+            ValidationError::ComparingIncompatibleTypes {
+                lhs: Type::String,
+                rhs: Type::Int32,
+            }
+        )
+    );
+    assert_eq!(
+        located("Age eq 1 and (Name eq Age)"),
+        (
+            "(Name eq Age)".to_owned(),
+            ValidationError::ComparingIncompatibleTypes {
+                lhs: Type::String,
+                rhs: Type::Int32,
+            }
+        )
+    );
+}
@@ -1,6 +1,8 @@
 use odata_params::bigdecimal::BigDecimal;
 use odata_params::chrono::{NaiveDate, NaiveTime, TimeZone, Utc};
-use odata_params::filters::{to_query_string, CompareOperator, Expr, Value};
+use odata_params::filters::{
+    parse_str, to_query_string, ArithmeticOperator, CompareOperator, Expr, LambdaOperator, Value,
+};
 
 #[test]
 fn or_grouping() {
@@ -49,7 +51,7 @@ fn not_grouping() {
     )));
 
     let result = to_query_string(&expr).expect("valid filter");
-    assert_eq!(result, "not name eq 'John'");
+    assert_eq!(result, "not (name eq 'John')");
 }
 
 #[test]
@@ -145,6 +147,21 @@ fn function_call_startswith() {
     assert_eq!(result, "startswith(name, 'J')");
 }
 
+#[test]
+fn type_functions() {
+    let expr = Expr::Function(
+        "cast".to_owned(),
+        vec![
+            Expr::Identifier("Price".to_owned()),
+            Expr::TypeName("Edm.String".to_owned()),
+        ],
+    );
+
+    let result = to_query_string(&expr).expect("valid filter");
+    assert_eq!(result, "cast(Price, Edm.String)");
+    assert_eq!(parse_str(&result), Ok(expr));
+}
+
 #[test]
 fn multiple_functions() {
     let expr = Expr::Compare(
@@ -250,7 +267,7 @@ fn nested_not() {
     )))));
 
     let result = to_query_string(&expr).expect("valid filter");
-    assert_eq!(result, "not not isActive eq true");
+    assert_eq!(result, "not not (isActive eq true)");
 }
 
 #[test]
@@ -259,13 +276,103 @@ fn datetime() {
     let expr = Expr::Compare(
         Box::new(Expr::Identifier("created".to_owned())),
         CompareOperator::Equal,
-        Box::new(Expr::Value(Value::DateTime(datetime))),
+        Box::new(Expr::Value(Value::DateTime(datetime.fixed_offset(), None))),
     );
 
     let result = to_query_string(&expr).expect("valid filter");
     assert_eq!(result, "created eq 2023-06-25T13:00:00.000Z");
 }
 
+#[test]
+fn datetime_round_trip() {
+    for filter in [
+        "created eq 2024-03-01T10:00:00.000Z",
+        "created eq 2024-03-01T10:00:00.000-05:00",
+        "created eq 2024-03-01T10:00:00.000+05:30",
+        "created eq 2024-03-01T10:00:00.123456Z",
+        "created eq 2024-03-01T10:00:00.000America/Toronto",
+        "created eq 2024-07-01T10:00:00.000America/Toronto",
+        "created eq 2024-03-01T10:00:00.000000001Europe/Paris",
+    ] {
+        let expr = parse_str(filter).expect("valid filter tree");
+        assert_eq!(to_query_string(&expr).unwrap(), filter);
+    }
+
+    let expr = parse_str("created eq 2024-03-01T10:00:00+01").expect("valid filter tree");
+    assert_eq!(
+        to_query_string(&expr).unwrap(),
+        "created eq 2024-03-01T10:00:00.000+01:00"
+    );
+}
+
+#[test]
+fn binary_round_trip() {
+    for filter in [
+        "Data eq binary'T0RhdGE='",
+        "Data eq binary'T0RhdGFz'",
+        "Data eq binary'T0Q='",
+        "Data eq binary'-_-_'",
+        "Data eq binary''",
+    ] {
+        let expr = parse_str(filter).expect("valid filter tree");
+        assert_eq!(to_query_string(&expr).unwrap(), filter);
+    }
+
+    let expr = parse_str("Data eq binary'T0RhdGE'").expect("valid filter tree");
+    assert_eq!(to_query_string(&expr).unwrap(), "Data eq binary'T0RhdGE='");
+}
+
+#[test]
+fn spatial_round_trip() {
+    for filter in [
+        "Location eq geography'SRID=4326;POINT(-73.5 45.5)'",
+        "Route eq geometry'LINESTRING(0 0,1 1)'",
+        "Area eq geography'POLYGON((0 0,1 0,1 1,0 0),(0.2 0.2,0.4 0.2,0.4 0.4,0.2 0.2))'",
+        "Stops eq geography'MULTIPOINT((0 0),(1 1))'",
+        "Routes eq geometry'MULTILINESTRING((0 0,1 1),(2 2,3 3))'",
+        "Areas eq geography'MULTIPOLYGON(((0 0,1 0,1 1,0 0)))'",
+        "Shapes eq geometry'COLLECTION(POINT(1 2),LINESTRING(0 0,1 1))'",
+        "geo.distance(Location, geography'POINT(1 2)') lt 1000",
+    ] {
+        let expr = parse_str(filter).expect("valid filter tree");
+        assert_eq!(to_query_string(&expr).unwrap(), filter);
+    }
+
+    let expr = parse_str("Location eq geography'srid=4326;Point( -73.5   45.5 )'")
+        .expect("valid filter tree");
+    assert_eq!(
+        to_query_string(&expr).unwrap(),
+        "Location eq geography'SRID=4326;POINT(-73.5 45.5)'"
+    );
+}
+
+#[test]
+fn collection_and_complex_round_trip() {
+    for filter in [
+        r#"Tags eq ["a","b\"c"]"#,
+        "Ids eq [1,-2,3.5]",
+        "Tags eq []",
+        r#"Address eq {"City":"Montreal","Zones":[1,null],"Extra":{}}"#,
+        r#"hasany(Tags, ["a\nb"])"#,
+    ] {
+        let expr = parse_str(filter).expect("valid filter tree");
+        assert_eq!(to_query_string(&expr).unwrap(), filter);
+    }
+
+    let expr = parse_str("Tags eq ['a', 'b']").expect("valid filter tree");
+    assert_eq!(to_query_string(&expr).unwrap(), r#"Tags eq ["a","b"]"#);
+
+    // Lists of `in` are always written in parentheses.
+    let expr = parse_str("Name in ['a','b']").expect("valid filter tree");
+    assert_eq!(to_query_string(&expr).unwrap(), "Name in ('a', 'b')");
+
+    // Collections given by an expression are written as they were parsed.
+    for filter in ["Name in @names", "Name in (@names)", "Name in Tags"] {
+        let expr = parse_str(filter).expect("valid filter tree");
+        assert_eq!(to_query_string(&expr).unwrap(), filter);
+    }
+}
+
 #[test]
 fn date() {
     let date = NaiveDate::from_ymd_opt(2023, 6, 25).expect("valid date");
@@ -303,3 +410,244 @@ fn null_value() {
     let result = to_query_string(&expr).expect("valid filter");
     assert_eq!(result, "description eq null");
 }
+
+#[test]
+fn arithmetic_minimal_parentheses() {
+    let number = |n: i32| Box::new(Expr::Value(Value::Number(BigDecimal::from(n))));
+    let identifier = |name: &str| Box::new(Expr::Identifier(name.to_owned()));
+
+    // (a add b) sub c
+    let expr = Expr::Arithmetic(
+        Box::new(Expr::Arithmetic(
+            identifier("a"),
+            ArithmeticOperator::Add,
+            identifier("b"),
+        )),
+        ArithmeticOperator::Sub,
+        identifier("c"),
+    );
+    assert_eq!(to_query_string(&expr).unwrap(), "a add b sub c");
+
+    // a sub (b add c)
+    let expr = Expr::Arithmetic(
+        identifier("a"),
+        ArithmeticOperator::Sub,
+        Box::new(Expr::Arithmetic(
+            identifier("b"),
+            ArithmeticOperator::Add,
+            identifier("c"),
+        )),
+    );
+    assert_eq!(to_query_string(&expr).unwrap(), "a sub (b add c)");
+
+    // (a add b) mul 2 gt c mod 3
+    let expr = Expr::Compare(
+        Box::new(Expr::Arithmetic(
+            Box::new(Expr::Arithmetic(
+                identifier("a"),
+                ArithmeticOperator::Add,
+                identifier("b"),
+            )),
+            ArithmeticOperator::Mul,
+            number(2),
+        )),
+        CompareOperator::GreaterThan,
+        Box::new(Expr::Arithmetic(
+            identifier("c"),
+            ArithmeticOperator::Mod,
+            number(3),
+        )),
+    );
+    assert_eq!(
+        to_query_string(&expr).unwrap(),
+        "(a add b) mul 2 gt c mod 3"
+    );
+}
+
+#[test]
+fn arithmetic_round_trip() {
+    for filter in [
+        "Price mul Quantity gt 100",
+        "Aa add Bb mul Cc sub Dd divby 2 le Ee div Ff",
+        "(Aa sub Bb) mod (Cc add Dd) eq 0",
+        "Aa sub (Bb sub Cc) ne Aa mul (Bb div Cc)",
+    ] {
+        let expr = parse_str(filter).expect("valid filter tree");
+        assert_eq!(to_query_string(&expr).unwrap(), filter);
+    }
+}
+
+#[test]
+fn negation() {
+    let expr = Expr::Compare(
+        Box::new(Expr::Negate(Box::new(Expr::Identifier("Price".to_owned())))),
+        CompareOperator::GreaterThan,
+        Box::new(Expr::Value(Value::Number(BigDecimal::from(-5)))),
+    );
+    assert_eq!(to_query_string(&expr).unwrap(), "-Price gt -5");
+
+    let expr = Expr::Negate(Box::new(Expr::Value(Value::Number(BigDecimal::from(5)))));
+    assert_eq!(to_query_string(&expr).unwrap(), "-(5)");
+
+    let expr = Expr::Negate(Box::new(Expr::Arithmetic(
+        Box::new(Expr::Identifier("Cost".to_owned())),
+        ArithmeticOperator::Sub,
+        Box::new(Expr::Identifier("Tax".to_owned())),
+    )));
+    assert_eq!(to_query_string(&expr).unwrap(), "-(Cost sub Tax)");
+}
+
+#[test]
+fn negation_round_trip() {
+    for filter in [
+        "Balance lt -100",
+        "-Price mul Qty sub -(5) gt -(Cost add Tax)",
+        "--Price eq Price",
+    ] {
+        let expr = parse_str(filter).expect("valid filter tree");
+        assert_eq!(to_query_string(&expr).unwrap(), filter);
+    }
+}
+
+#[test]
+fn typed_number_round_trip() {
+    for filter in [
+        "Count eq 42L",
+        "Price eq 4.5m",
+        "Ratio eq 0.25f",
+        "Distance eq 1.5e3",
+        "Distance eq -2e-2",
+        "Distance eq 4.5e0",
+        "Distance eq 0e0",
+        "Ratio eq 0.00000000000000000001",
+        "Count eq 100000000000000000000",
+        "Price eq 0.00000000000000000001m",
+        "Distance lt INF and Distance gt -INF",
+        "Distance ne NaN",
+        "-(INF) eq -INF",
+    ] {
+        let expr = parse_str(filter).expect("valid filter tree");
+        assert_eq!(to_query_string(&expr).unwrap(), filter);
+    }
+
+    for (filter, expected) in [
+        ("Distance eq 15e2", "Distance eq 1.5e3"),
+        ("Distance eq 4.5d", "Distance eq 4.5e0"),
+        ("Distance eq 1.50e-3", "Distance eq 1.5e-3"),
+    ] {
+        let expr = parse_str(filter).expect("valid filter tree");
+        assert_eq!(to_query_string(&expr).unwrap(), expected);
+    }
+
+    // Small and large numbers keep their type, written without an exponent.
+    let expr = parse_str("x eq 0.00000000000000000001").expect("valid filter tree");
+    assert_eq!(parse_str(to_query_string(&expr).unwrap()), Ok(expr));
+}
+
+#[test]
+fn duration_round_trip() {
+    for filter in [
+        "Elapsed lt duration'P1DT2H30M'",
+        "Elapsed lt duration'-PT1.5S'",
+        "Elapsed eq duration'PT0S'",
+        "Elapsed eq duration'P3D'",
+        "CreatedAt gt now() sub duration'PT12H0.000000001S'",
+        "-duration'P1D' eq duration'-P1D'",
+    ] {
+        let expr = parse_str(filter).expect("valid filter tree");
+        assert_eq!(to_query_string(&expr).unwrap(), filter);
+    }
+
+    let expr = parse_str("Elapsed gt PT36H").expect("valid filter tree");
+    assert_eq!(
+        to_query_string(&expr).unwrap(),
+        "Elapsed gt duration'P1DT12H'"
+    );
+}
+
+#[test]
+fn has_operator() {
+    let expr = Expr::Has(
+        Box::new(Expr::Identifier("Style".to_owned())),
+        Box::new(Expr::Value(Value::Enum {
+            type_name: "Sales.Color".to_owned(),
+            members: vec!["Red".to_owned(), "Blue".to_owned()],
+        })),
+    );
+
+    let result = to_query_string(&expr).expect("valid filter");
+    assert_eq!(result, "Style has Sales.Color'Red,Blue'");
+}
+
+#[test]
+fn has_operator_round_trip() {
+    for filter in [
+        "Style has Sales.Color'Yellow'",
+        "not Style has Sales.Color'Red,Blue'",
+        "Style has Sales.Color'Red' eq Style has Sales.Color'Blue'",
+    ] {
+        let expr = parse_str(filter).expect("valid filter tree");
+        assert_eq!(to_query_string(&expr).unwrap(), filter);
+    }
+}
+
+#[test]
+fn lambda() {
+    let expr = Expr::Lambda(
+        Box::new(Expr::Identifier("Tags".to_owned())),
+        LambdaOperator::Any,
+        Some((
+            "t".to_owned(),
+            Box::new(Expr::Or(
+                Box::new(Expr::Compare(
+                    Box::new(Expr::Identifier("t".to_owned())),
+                    CompareOperator::Equal,
+                    Box::new(Expr::Value(Value::String("urgent".to_owned()))),
+                )),
+                Box::new(Expr::Compare(
+                    Box::new(Expr::Identifier("t".to_owned())),
+                    CompareOperator::Equal,
+                    Box::new(Expr::Value(Value::String("late".to_owned()))),
+                )),
+            )),
+        )),
+    );
+
+    let result = to_query_string(&expr).expect("valid filter");
+    assert_eq!(result, "Tags/any(t: t eq 'urgent' or t eq 'late')");
+}
+
+#[test]
+fn lambda_round_trip() {
+    for filter in [
+        "Tags/any(t: t eq 'urgent')",
+        "Orders/any() and Groups/all(g: Members/any(m: m eq g))",
+    ] {
+        let expr = parse_str(filter).expect("valid filter tree");
+        assert_eq!(to_query_string(&expr).unwrap(), filter);
+    }
+}
+
+#[test]
+fn parameter_alias() {
+    let expr = Expr::Compare(
+        Box::new(Expr::Identifier("Price".to_owned())),
+        CompareOperator::GreaterThan,
+        Box::new(Expr::Alias("minPrice".to_owned())),
+    );
+
+    let result = to_query_string(&expr).expect("valid filter");
+    assert_eq!(result, "Price gt @minPrice");
+}
+
+#[test]
+fn member_path_round_trip() {
+    for filter in [
+        "Address/City eq 'Paris'",
+        "Customer/Country/Code in ('CA', 'US')",
+        "Customer/Orders/all(o: o/Total mul -o/Quantity gt 10)",
+    ] {
+        let expr = parse_str(filter).expect("valid filter tree");
+        assert_eq!(to_query_string(&expr).unwrap(), filter);
+    }
+}
@@ -1,6 +1,8 @@
 use bigdecimal::BigDecimal;
 use odata_params::filters::{
-    CompareOperator, Expr, FunctionsTypeMap, IdentifiersTypeMap, Type, ValidationError, Value,
+    parse_str, parse_str_with_spans, ArithmeticOperator, CompareOperator, Expr, FunctionSignature,
+    FunctionsTypeMap, IdentifiersTypeMap, LambdaOperator, NodeValidationError, Span, Type,
+    ValidationError, Value,
 };
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -17,7 +19,7 @@ fn test_literal_values() {
     assert_eq!(
         Expr::Value(Value::Number(BigDecimal::from_str("42").unwrap()))
             .validate(&type_map, &functions_map),
-        Ok(Type::Number)
+        Ok(Type::Int32)
     );
     assert_eq!(
         Expr::Value(Value::String("hello".to_string())).validate(&type_map, &functions_map),
@@ -28,13 +30,13 @@ fn test_literal_values() {
 #[test]
 fn test_identifiers() {
     let mut id_map = HashMap::new();
-    id_map.insert("abc".to_string(), Type::Number);
+    id_map.insert("abc".to_string(), Type::Int32);
     let type_map = IdentifiersTypeMap::from(id_map);
     let functions_map = FunctionsTypeMap::from(HashMap::new());
 
     assert_eq!(
         Expr::Identifier("abc".to_string()).validate(&type_map, &functions_map),
-        Ok(Type::Number)
+        Ok(Type::Int32)
     );
     assert_eq!(
         Expr::Identifier("unknown".to_string()).validate(&type_map, &functions_map),
@@ -47,7 +49,7 @@ fn test_identifiers() {
 #[test]
 fn test_comparisons() {
     let mut id_map = HashMap::new();
-    id_map.insert("abc".to_string(), Type::Number);
+    id_map.insert("abc".to_string(), Type::Int32);
     let type_map = IdentifiersTypeMap::from(id_map);
     let functions_map = FunctionsTypeMap::from(HashMap::new());
 
@@ -68,7 +70,7 @@ fn test_comparisons() {
     assert_eq!(
         expr.validate(&type_map, &functions_map),
         Err(ValidationError::ComparingIncompatibleTypes {
-            lhs: Type::Number,
+            lhs: Type::Int32,
             rhs: Type::String,
         })
     );
@@ -100,7 +102,7 @@ fn test_logical_operations() {
 #[test]
 fn test_in_operator() {
     let mut id_map = HashMap::new();
-    id_map.insert("id".to_string(), Type::Number);
+    id_map.insert("id".to_string(), Type::Int32);
     let type_map = IdentifiersTypeMap::from(id_map);
     let functions_map = FunctionsTypeMap::from(HashMap::new());
 
@@ -123,7 +125,7 @@ fn test_in_operator() {
     assert_eq!(
         expr.validate(&type_map, &functions_map),
         Err(ValidationError::ComparingIncompatibleTypes {
-            lhs: Type::Number,
+            lhs: Type::Int32,
             rhs: Type::String,
         })
     );
@@ -132,14 +134,14 @@ fn test_in_operator() {
 #[test]
 fn test_function_call() {
     let mut id_map = HashMap::new();
-    id_map.insert("arg1".to_string(), Type::Number);
+    id_map.insert("arg1".to_string(), Type::Int32);
     id_map.insert("arg2".to_string(), Type::String);
     let type_map = IdentifiersTypeMap::from(id_map);
 
     let mut func_map = HashMap::new();
     func_map.insert(
         "test_func".to_string(),
-        (vec![Type::Number, Type::Number], None, Type::Boolean),
+        (vec![Type::Int32, Type::Int32], None, Type::Boolean),
     );
     func_map.insert(
         "variadic_func".to_string(),
@@ -168,7 +170,7 @@ fn test_function_call() {
         Err(ValidationError::IncorrectFunctionArgumentType {
             name: "test_func".to_string(),
             position: 2,
-            expected: Type::Number,
+            expected: Type::Int32,
             given: Type::String,
         })
     );
@@ -183,3 +185,1296 @@ fn test_function_call() {
     );
     assert_eq!(expr.validate(&type_map, &functions_map), Ok(Type::Boolean));
 }
+
+#[test]
+fn test_arithmetic() {
+    let mut id_map = HashMap::new();
+    id_map.insert("price".to_string(), Type::Int32);
+    id_map.insert("name".to_string(), Type::String);
+    let type_map = IdentifiersTypeMap::from(id_map);
+    let functions_map = FunctionsTypeMap::from(HashMap::new());
+
+    let expr = Expr::Arithmetic(
+        Box::new(Expr::Identifier("price".to_string())),
+        ArithmeticOperator::Mul,
+        Box::new(Expr::Value(Value::Number(
+            BigDecimal::from_str("2").unwrap(),
+        ))),
+    );
+    assert_eq!(expr.validate(&type_map, &functions_map), Ok(Type::Int32));
+
+    let expr = Expr::Arithmetic(
+        Box::new(Expr::Identifier("price".to_string())),
+        ArithmeticOperator::Add,
+        Box::new(Expr::Identifier("name".to_string())),
+    );
+    assert_eq!(
+        expr.validate(&type_map, &functions_map),
+        Err(ValidationError::ArithmeticOnIncompatibleTypes {
+            operator: ArithmeticOperator::Add,
+            lhs: Type::Int32,
+            rhs: Type::String,
+        })
+    );
+}
+
+#[test]
+fn test_negation() {
+    let mut id_map = HashMap::new();
+    id_map.insert("price".to_string(), Type::Int32);
+    id_map.insert("name".to_string(), Type::String);
+    let type_map = IdentifiersTypeMap::from(id_map);
+    let functions_map = FunctionsTypeMap::from(HashMap::new());
+
+    let expr = Expr::Negate(Box::new(Expr::Identifier("price".to_string())));
+    assert_eq!(expr.validate(&type_map, &functions_map), Ok(Type::Int32));
+
+    let expr = Expr::Negate(Box::new(Expr::Identifier("name".to_string())));
+    assert_eq!(
+        expr.validate(&type_map, &functions_map),
+        Err(ValidationError::NegationRequiresNumber {
+            given: Type::String,
+        })
+    );
+}
+
+#[test]
+fn test_has_operator() {
+    let mut id_map = HashMap::new();
+    id_map.insert("style".to_string(), Type::Enum("Sales.Color".to_string()));
+    id_map.insert("size".to_string(), Type::Enum("Sales.Size".to_string()));
+    let type_map = IdentifiersTypeMap::from(id_map)
+        .with_enum("Sales.Color", ["Red", "Blue", "Yellow"])
+        .with_enum("Sales.Size", ["Small", "Large"]);
+    let functions_map = FunctionsTypeMap::from(HashMap::new());
+
+    let color = |members: &[&str]| {
+        Box::new(Expr::Value(Value::Enum {
+            type_name: "Sales.Color".to_string(),
+            members: members.iter().map(|m| m.to_string()).collect(),
+        }))
+    };
+
+    let expr = Expr::Has(
+        Box::new(Expr::Identifier("style".to_string())),
+        color(&["Red", "Blue"]),
+    );
+    assert_eq!(expr.validate(&type_map, &functions_map), Ok(Type::Boolean));
+
+    let expr = Expr::Has(
+        Box::new(Expr::Identifier("size".to_string())),
+        color(&["Red"]),
+    );
+    assert_eq!(
+        expr.validate(&type_map, &functions_map),
+        Err(ValidationError::HasRequiresMatchingEnums {
+            lhs: Type::Enum("Sales.Size".to_string()),
+            rhs: Type::Enum("Sales.Color".to_string()),
+        })
+    );
+
+    let expr = Expr::Has(
+        Box::new(Expr::Identifier("style".to_string())),
+        color(&["Red", "Purple"]),
+    );
+    assert_eq!(
+        expr.validate(&type_map, &functions_map),
+        Err(ValidationError::UndefinedEnumMember {
+            type_name: "Sales.Color".to_string(),
+            member: "Purple".to_string(),
+        })
+    );
+
+    let expr = Expr::Value(Value::Enum {
+        type_name: "Sales.Shape".to_string(),
+        members: vec!["Round".to_string()],
+    });
+    assert_eq!(
+        expr.validate(&type_map, &functions_map),
+        Err(ValidationError::UndefinedEnumType {
+            name: "Sales.Shape".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_lambda() {
+    let mut id_map = HashMap::new();
+    id_map.insert("tags".to_string(), Type::Collection(Box::new(Type::String)));
+    id_map.insert("name".to_string(), Type::String);
+    id_map.insert("t".to_string(), Type::Int32);
+    let type_map = IdentifiersTypeMap::from(id_map);
+    let functions_map = FunctionsTypeMap::from(HashMap::new());
+
+    // The range variable `t` shadows the `t` identifier.
+    let expr = Expr::Lambda(
+        Box::new(Expr::Identifier("tags".to_string())),
+        LambdaOperator::Any,
+        Some((
+            "t".to_string(),
+            Box::new(Expr::Compare(
+                Box::new(Expr::Identifier("t".to_string())),
+                CompareOperator::Equal,
+                Box::new(Expr::Identifier("name".to_string())),
+            )),
+        )),
+    );
+    assert_eq!(expr.validate(&type_map, &functions_map), Ok(Type::Boolean));
+
+    let expr = Expr::Lambda(
+        Box::new(Expr::Identifier("tags".to_string())),
+        LambdaOperator::Any,
+        None,
+    );
+    assert_eq!(expr.validate(&type_map, &functions_map), Ok(Type::Boolean));
+
+    let expr = Expr::Lambda(
+        Box::new(Expr::Identifier("name".to_string())),
+        LambdaOperator::All,
+        Some(("x".to_string(), Box::new(Expr::Value(Value::Bool(true))))),
+    );
+    assert_eq!(
+        expr.validate(&type_map, &functions_map),
+        Err(ValidationError::LambdaRequiresCollection {
+            operator: LambdaOperator::All,
+            given: Type::String,
+        })
+    );
+
+    let expr = Expr::Lambda(
+        Box::new(Expr::Identifier("tags".to_string())),
+        LambdaOperator::All,
+        Some(("x".to_string(), Box::new(Expr::Identifier("x".to_string())))),
+    );
+    assert_eq!(
+        expr.validate(&type_map, &functions_map),
+        Err(ValidationError::LambdaRequiresBooleanPredicate {
+            operator: LambdaOperator::All,
+            given: Type::String,
+        })
+    );
+
+    // The range variable is not in scope outside of its lambda.
+    let expr = Expr::And(
+        Box::new(Expr::Lambda(
+            Box::new(Expr::Identifier("tags".to_string())),
+            LambdaOperator::Any,
+            None,
+        )),
+        Box::new(Expr::Identifier("x".to_string())),
+    );
+    assert_eq!(
+        expr.validate(&type_map, &functions_map),
+        Err(ValidationError::UndefinedIdentifier {
+            name: "x".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_unresolved_alias() {
+    let type_map = IdentifiersTypeMap::from(HashMap::new());
+    let functions_map = FunctionsTypeMap::from(HashMap::new());
+
+    assert_eq!(
+        Expr::Alias("p".to_string()).validate(&type_map, &functions_map),
+        Err(ValidationError::UnresolvedAlias {
+            name: "p".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_member_paths() {
+    let mut id_map = HashMap::new();
+    id_map.insert(
+        "Customer".to_string(),
+        Type::Complex("Sales.Customer".to_string()),
+    );
+    let type_map = IdentifiersTypeMap::from(id_map)
+        .with_complex_type(
+            "Sales.Customer",
+            [
+                ("Name", Type::String),
+                ("Address", Type::Complex("Sales.Address".to_string())),
+                (
+                    "Orders",
+                    Type::Collection(Box::new(Type::Complex("Sales.Order".to_string()))),
+                ),
+            ],
+        )
+        .with_complex_type("Sales.Address", [("City", Type::String)])
+        .with_complex_type("Sales.Order", [("Total", Type::Int32)]);
+    let functions_map = FunctionsTypeMap::from(HashMap::new());
+
+    let path = |segments: &[&str]| Expr::Path(segments.iter().map(|s| s.to_string()).collect());
+
+    assert_eq!(
+        path(&["Customer", "Address", "City"]).validate(&type_map, &functions_map),
+        Ok(Type::String)
+    );
+
+    // Range variables are resolved through the member type of the collection.
+    let expr = Expr::Lambda(
+        Box::new(path(&["Customer", "Orders"])),
+        LambdaOperator::All,
+        Some((
+            "o".to_string(),
+            Box::new(Expr::Compare(
+                Box::new(path(&["o", "Total"])),
+                CompareOperator::GreaterThan,
+                Box::new(Expr::Value(Value::Number(BigDecimal::from(10)))),
+            )),
+        )),
+    );
+    assert_eq!(expr.validate(&type_map, &functions_map), Ok(Type::Boolean));
+
+    assert_eq!(
+        path(&["Customer", "Phone"]).validate(&type_map, &functions_map),
+        Err(ValidationError::UndefinedProperty {
+            type_name: "Sales.Customer".to_string(),
+            property: "Phone".to_string(),
+        })
+    );
+
+    assert_eq!(
+        path(&["Customer", "Name", "First"]).validate(&type_map, &functions_map),
+        Err(ValidationError::PropertyAccessRequiresComplexType {
+            property: "First".to_string(),
+            given: Type::String,
+        })
+    );
+
+    assert_eq!(
+        path(&["Customer", "Orders", "Total"]).validate(&type_map, &functions_map),
+        Err(ValidationError::PropertyAccessRequiresComplexType {
+            property: "Total".to_string(),
+            given: Type::Collection(Box::new(Type::Complex("Sales.Order".to_string()))),
+        })
+    );
+
+    let type_map = IdentifiersTypeMap::from(HashMap::from([(
+        "Address".to_string(),
+        Type::Complex("Sales.Address".to_string()),
+    )]));
+    assert_eq!(
+        path(&["Address", "City"]).validate(&type_map, &functions_map),
+        Err(ValidationError::UndefinedComplexType {
+            name: "Sales.Address".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_collect_all_errors() {
+    let type_map = IdentifiersTypeMap::from(HashMap::from([
+        ("name".to_string(), Type::String),
+        ("age".to_string(), Type::Int32),
+    ]));
+    let functions_map = FunctionsTypeMap::from(HashMap::from([(
+        "length".to_string(),
+        (vec![Type::String], None, Type::Int32),
+    )]));
+
+    let (expr, spans) =
+        parse_str_with_spans("length(age) gt 3 or name eq 5 or city eq 'Paris' or age gt 1")
+            .expect("valid filter tree");
+
+    let errors = expr
+        .validate_all(&type_map, &functions_map)
+        .expect_err("invalid filter");
+
+    assert_eq!(
+        errors,
+        vec![
+            NodeValidationError {
+                path: vec![0, 0, 0, 0, 0],
+                error: ValidationError::IncorrectFunctionArgumentType {
+                    name: "length".to_string(),
+                    position: 1,
+                    expected: Type::String,
+                    given: Type::Int32,
+                },
+            },
+            NodeValidationError {
+                path: vec![0, 0, 1],
+                error: ValidationError::ComparingIncompatibleTypes {
+                    lhs: Type::String,
+                    rhs: Type::Int32,
+                },
+            },
+            NodeValidationError {
+                path: vec![0, 1, 0],
+                error: ValidationError::UndefinedIdentifier {
+                    name: "city".to_string(),
+                },
+            },
+        ]
+    );
+
+    // The first collected error is the one `validate` stops at.
+    assert_eq!(
+        expr.validate(&type_map, &functions_map),
+        Err(errors[0].error.clone())
+    );
+
+    let located: Vec<_> = errors
+        .iter()
+        .map(|error| spans.get(&error.path).expect("known node").span)
+        .collect();
+    assert_eq!(
+        located,
+        vec![
+            Span { start: 7, end: 10 },
+            Span { start: 20, end: 29 },
+            Span { start: 33, end: 37 },
+        ]
+    );
+}
+
+#[test]
+fn test_collect_errors_without_cascading() {
+    let type_map = IdentifiersTypeMap::from(HashMap::new());
+    let functions_map = FunctionsTypeMap::from(HashMap::new());
+
+    // Errors in operands are not reported again for their parents, nor for
+    // the predicate of a lambda over an invalid collection.
+    let expr = parse_str("not (missing add 1 eq 2) and Items/any(i: i/Price gt 1) and foo(bar)")
+        .expect("valid filter tree");
+
+    assert_eq!(
+        expr.validate_all(&type_map, &functions_map),
+        Err(vec![
+            NodeValidationError {
+                path: vec![0, 0, 0, 0, 0],
+                error: ValidationError::UndefinedIdentifier {
+                    name: "missing".to_string(),
+                },
+            },
+            NodeValidationError {
+                path: vec![0, 1, 0],
+                error: ValidationError::UndefinedIdentifier {
+                    name: "Items".to_string(),
+                },
+            },
+            NodeValidationError {
+                path: vec![1],
+                error: ValidationError::UndefinedFunction {
+                    name: "foo".to_string(),
+                },
+            },
+            NodeValidationError {
+                path: vec![1, 0],
+                error: ValidationError::UndefinedIdentifier {
+                    name: "bar".to_string(),
+                },
+            },
+        ])
+    );
+}
+
+#[test]
+fn test_standard_functions() {
+    let type_map = IdentifiersTypeMap::from(HashMap::from([
+        ("Name".to_string(), Type::String),
+        ("Price".to_string(), Type::Int32),
+        ("CreatedAt".to_string(), Type::DateTime),
+        ("Tags".to_string(), Type::Collection(Box::new(Type::String))),
+        (
+            "Scores".to_string(),
+            Type::Collection(Box::new(Type::Int64)),
+        ),
+        (
+            "Aliases".to_string(),
+            Type::Collection(Box::new(Type::String.nullable())),
+        ),
+    ]));
+    let functions_map = FunctionsTypeMap::odata_standard();
+
+    let validate = |filter: &str| {
+        parse_str(filter)
+            .expect("valid filter tree")
+            .validate(&type_map, &functions_map)
+    };
+
+    assert_eq!(
+        validate("startswith(toupper(trim(Name)), 'J') and length(Name) lt 20"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        validate("substring(Name, 1) eq 'ohn' and substring(Name, 1, 2) eq 'oh'"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        validate("round(Price) eq floor(Price) or ceiling(Price) gt 10"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        validate("year(CreatedAt) eq 2024 and CreatedAt lt now()"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(validate("date(CreatedAt)"), Ok(Type::Date));
+    assert!(matches!(
+        validate("contains(Name, 5)"),
+        Err(ValidationError::NoMatchingFunctionOverload { .. })
+    ));
+
+    // Collection functions accept collections of any primitive type.
+    assert_eq!(
+        validate("contains(Tags, 'a') and hassubset(Tags, ['a', 'b'])"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        validate("hassubsequence(Scores, [1, 2])"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(validate("contains(Scores, 1)"), Ok(Type::Boolean));
+    assert_eq!(
+        validate("concat(Tags, ['c'])"),
+        Ok(Type::Collection(Box::new(Type::String)))
+    );
+    assert!(matches!(
+        validate("contains(Tags, 1)"),
+        Err(ValidationError::NoMatchingFunctionOverload { .. })
+    ));
+
+    // The string functions also accept sequences given as collections.
+    assert_eq!(validate("length(Tags) gt 2"), Ok(Type::Boolean));
+    assert_eq!(validate("indexof(Scores, [2, 3])"), Ok(Type::Int32));
+    assert_eq!(
+        validate("startswith(Tags, ['a']) and endswith(Tags, ['z'])"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(validate("contains(Scores, [2, 3])"), Ok(Type::Boolean));
+    assert_eq!(
+        validate("substring(Tags, 1)"),
+        Ok(Type::Collection(Box::new(Type::String)))
+    );
+    assert_eq!(
+        validate("substring(Scores, 1, 2)"),
+        Ok(Type::Collection(Box::new(Type::Int64)))
+    );
+    assert!(matches!(
+        validate("startswith(Tags, [1])"),
+        Err(ValidationError::NoMatchingFunctionOverload { .. })
+    ));
+
+    // Members of collections match whether or not they can be null.
+    assert_eq!(validate("contains(Aliases, 'a')"), Ok(Type::Boolean));
+    assert_eq!(
+        validate("hassubset(Aliases, ['a', null])"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(validate("length(Aliases)"), Ok(Type::Int32));
+    assert_eq!(validate("hassubset(Tags, ['a', null])"), Ok(Type::Boolean));
+
+    assert_eq!(
+        validate("has_role('admin')"),
+        Err(ValidationError::UndefinedFunction {
+            name: "has_role".to_string()
+        })
+    );
+
+    // Custom functions are added to the catalog, and merged maps add
+    // overloads to the standard ones.
+    let functions_map = FunctionsTypeMap::odata_standard()
+        .with_function("has_role", [Type::String], None, Type::Boolean)
+        .merge(FunctionsTypeMap::from(HashMap::from([(
+            "length".to_string(),
+            (vec![Type::Int32], None, Type::Int32),
+        )])));
+
+    let validate = |filter: &str| {
+        parse_str(filter)
+            .expect("valid filter tree")
+            .validate(&type_map, &functions_map)
+    };
+
+    assert_eq!(
+        validate("has_role('admin') and contains(Name, 'o')"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(validate("length(Price)"), Ok(Type::Int32));
+    assert_eq!(validate("length(Name)"), Ok(Type::Int32));
+}
+
+#[test]
+fn test_function_overloads() {
+    let type_map = IdentifiersTypeMap::from(HashMap::from([
+        ("Name".to_string(), Type::String),
+        ("Price".to_string(), Type::Int32),
+        ("Born".to_string(), Type::Date),
+        ("Opens".to_string(), Type::Time),
+    ]));
+    let functions_map = FunctionsTypeMap::odata_standard()
+        .with_function("pad", [Type::String], Some(Type::Int32), Type::String)
+        .with_function("pad", [Type::Int32, Type::Int32], None, Type::Int32);
+
+    let validate = |filter: &str| {
+        parse_str(filter)
+            .expect("valid filter tree")
+            .validate(&type_map, &functions_map)
+    };
+
+    assert_eq!(validate("year(Born)"), Ok(Type::Int32));
+    assert_eq!(validate("hour(Opens) lt 9"), Ok(Type::Boolean));
+    assert_eq!(validate("pad(Name, 1, 2, 3)"), Ok(Type::String));
+    assert_eq!(validate("pad(Price, 2)"), Ok(Type::Int32));
+
+    let Err(ValidationError::NoMatchingFunctionOverload {
+        name,
+        given,
+        candidates,
+    }) = validate("substring(Name)")
+    else {
+        panic!("expected no matching overload");
+    };
+    assert_eq!(name, "substring");
+    assert_eq!(given, vec![Type::String]);
+    // The string overloads are listed before the collection ones.
+    assert_eq!(
+        candidates[..2],
+        [
+            FunctionSignature {
+                args: vec![Type::String, Type::Int32],
+                variadic: None,
+                ret: Type::String,
+            },
+            FunctionSignature {
+                args: vec![Type::String, Type::Int32, Type::Int32],
+                variadic: None,
+                ret: Type::String,
+            },
+        ]
+    );
+
+    let error = validate("hour(Born)").expect_err("no overload for dates");
+    assert_eq!(
+        error.to_string(),
+        "No overload of function 'hour' accepts arguments [Date], candidates are: (DateTime) -> Int32, (Time) -> Int32."
+    );
+
+    let error = validate("pad(Price)").expect_err("no overload for a single number");
+    assert_eq!(
+        error.to_string(),
+        "No overload of function 'pad' accepts arguments [Int32], candidates are: (String, Int32...) -> String, (Int32, Int32) -> Int32."
+    );
+
+    // Errors within the arguments are reported instead of the overloads.
+    assert_eq!(
+        validate("year(Missing)"),
+        Err(ValidationError::UndefinedIdentifier {
+            name: "Missing".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_numeric_promotion() {
+    let type_map = IdentifiersTypeMap::from(HashMap::from([
+        ("Name".to_string(), Type::String),
+        ("Age".to_string(), Type::Int32),
+        ("Views".to_string(), Type::Int64),
+        ("Price".to_string(), Type::Decimal),
+        ("Distance".to_string(), Type::Double),
+    ]));
+    let functions_map = FunctionsTypeMap::odata_standard();
+
+    let validate = |filter: &str| {
+        parse_str(filter)
+            .expect("valid filter tree")
+            .validate(&type_map, &functions_map)
+    };
+
+    // Literals are typed by their value, suffix or exponent.
+    assert_eq!(validate("42"), Ok(Type::Int32));
+    assert_eq!(validate("2147483648"), Ok(Type::Int64));
+    assert_eq!(validate("42L"), Ok(Type::Int64));
+    assert_eq!(validate("4.5"), Ok(Type::Decimal));
+    assert_eq!(validate("4.5m"), Ok(Type::Decimal));
+    assert_eq!(validate("1.5e3"), Ok(Type::Double));
+    assert_eq!(validate("4.5f"), Ok(Type::Single));
+    assert_eq!(validate("-INF"), Ok(Type::Double));
+
+    // Operands are promoted to the wider numeric type.
+    assert_eq!(validate("Age add 1"), Ok(Type::Int32));
+    assert_eq!(validate("Age mul Views"), Ok(Type::Int64));
+    assert_eq!(validate("Age add Price"), Ok(Type::Decimal));
+    assert_eq!(validate("Price div 2.5f"), Ok(Type::Single));
+    assert_eq!(validate("Age div 2"), Ok(Type::Int32));
+    assert_eq!(validate("Age divby 2"), Ok(Type::Decimal));
+    assert_eq!(validate("Age divby 2 eq 1.5"), Ok(Type::Boolean));
+    assert_eq!(validate("Distance divby 2"), Ok(Type::Double));
+    assert_eq!(validate("-Price mul Distance"), Ok(Type::Double));
+    assert_eq!(validate("round(Age)"), Ok(Type::Decimal));
+    assert_eq!(validate("round(Distance)"), Ok(Type::Double));
+    assert_eq!(
+        validate("Age eq 30 and Views gt Age and Price lt 9.99 and Distance lt INF"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(validate("Age gt 3.5 and Age in (1, 2)"), Ok(Type::Boolean));
+
+    // Integers can never equal a fractional literal.
+    assert_eq!(
+        validate("Age eq 3.5"),
+        Err(ValidationError::ComparingIncompatibleTypes {
+            lhs: Type::Int32,
+            rhs: Type::Decimal,
+        })
+    );
+    assert_eq!(
+        validate("1.5e-1 ne Views"),
+        Err(ValidationError::ComparingIncompatibleTypes {
+            lhs: Type::Double,
+            rhs: Type::Int64,
+        })
+    );
+    assert_eq!(
+        validate("Age in (1, 2.5)"),
+        Err(ValidationError::ComparingIncompatibleTypes {
+            lhs: Type::Int32,
+            rhs: Type::Decimal,
+        })
+    );
+
+    // Arguments are only promoted to wider types.
+    let Err(ValidationError::NoMatchingFunctionOverload {
+        name,
+        given,
+        candidates,
+    }) = validate("substring(Name, Views)")
+    else {
+        panic!("expected no matching overload");
+    };
+    assert_eq!(name, "substring");
+    assert_eq!(given, vec![Type::String, Type::Int64]);
+    // The string overloads are listed before the collection ones.
+    assert_eq!(
+        candidates[..2],
+        [
+            FunctionSignature {
+                args: vec![Type::String, Type::Int32],
+                variadic: None,
+                ret: Type::String,
+            },
+            FunctionSignature {
+                args: vec![Type::String, Type::Int32, Type::Int32],
+                variadic: None,
+                ret: Type::String,
+            },
+        ]
+    );
+
+    assert_eq!(
+        validate("Name add 1"),
+        Err(ValidationError::ArithmeticOnIncompatibleTypes {
+            operator: ArithmeticOperator::Add,
+            lhs: Type::String,
+            rhs: Type::Int32,
+        })
+    );
+}
+
+#[test]
+fn test_nullable_types() {
+    let type_map = IdentifiersTypeMap::from(HashMap::from([
+        ("Name".to_string(), Type::String),
+        ("MiddleName".to_string(), Type::String.nullable()),
+        ("Age".to_string(), Type::Int32.nullable()),
+        ("IsActive".to_string(), Type::Boolean),
+        (
+            "Address".to_string(),
+            Type::Complex("Sales.Address".to_string()).nullable(),
+        ),
+        (
+            "Home".to_string(),
+            Type::Complex("Sales.Address".to_string()),
+        ),
+    ]))
+    .with_complex_type(
+        "Sales.Address",
+        [("City", Type::String), ("Unit", Type::String.nullable())],
+    );
+    let functions_map = FunctionsTypeMap::odata_standard().with_function(
+        "coalesce",
+        [Type::String.nullable(), Type::String],
+        None,
+        Type::String,
+    );
+
+    let validate = |filter: &str| {
+        parse_str(filter)
+            .expect("valid filter tree")
+            .validate(&type_map, &functions_map)
+    };
+
+    assert_eq!(
+        validate("MiddleName eq null and null ne Age"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(validate("MiddleName in ('A', null)"), Ok(Type::Boolean));
+    assert_eq!(validate("Age add 1 gt 30"), Ok(Type::Boolean));
+    assert_eq!(validate("Age add 1"), Ok(Type::Int32));
+
+    // Arithmetic on nullable operands is nullable too.
+    assert_eq!(
+        validate("Age add 1 eq null and -Age eq null and 2 mul Age ne null"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        validate("length(Name) add 1 eq null"),
+        Err(ValidationError::ComparingNullWithNonNullable { given: Type::Int32 })
+    );
+    assert_eq!(validate("MiddleName"), Ok(Type::String));
+
+    // Functions given a nullable value for a parameter that is not
+    // nullable return null along with it.
+    assert_eq!(
+        validate("length(MiddleName) eq null and concat(Name, MiddleName) eq null"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        validate("coalesce(Name, MiddleName) eq null"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        validate("coalesce(MiddleName, Name) eq null"),
+        Err(ValidationError::ComparingNullWithNonNullable {
+            given: Type::String
+        })
+    );
+
+    // Members of nullable values are nullable along with their own declarations.
+    assert_eq!(
+        validate("Address/City eq null and Home/Unit eq null"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        validate("Home/City eq null"),
+        Err(ValidationError::ComparingNullWithNonNullable {
+            given: Type::String
+        })
+    );
+
+    assert_eq!(
+        validate("Name eq null"),
+        Err(ValidationError::ComparingNullWithNonNullable {
+            given: Type::String
+        })
+    );
+    assert_eq!(
+        validate("Name in ('A', null)"),
+        Err(ValidationError::ComparingNullWithNonNullable {
+            given: Type::String
+        })
+    );
+
+    // Only equality is allowed with null.
+    assert_eq!(
+        validate("Age gt null"),
+        Err(ValidationError::UnsupportedComparisonOperator {
+            operator: CompareOperator::GreaterThan,
+            given: Type::Null,
+        })
+    );
+    assert_eq!(
+        validate("null gt 5"),
+        Err(ValidationError::UnsupportedComparisonOperator {
+            operator: CompareOperator::GreaterThan,
+            given: Type::Null,
+        })
+    );
+    assert_eq!(
+        validate("not null"),
+        Err(ValidationError::LogicalNotRequiresBoolean { given: Type::Null })
+    );
+    assert_eq!(
+        validate("IsActive and null"),
+        Err(ValidationError::LogicalJoinRequiresBooleans {
+            lhs: Type::Boolean,
+            rhs: Type::Null,
+        })
+    );
+
+    // Functions only accept null for nullable arguments.
+    assert_eq!(validate("coalesce(null, Name)"), Ok(Type::String));
+    assert_eq!(validate("coalesce(MiddleName, Name)"), Ok(Type::String));
+    assert_eq!(
+        validate("coalesce(MiddleName, null)"),
+        Err(ValidationError::IncorrectFunctionArgumentType {
+            name: "coalesce".to_string(),
+            position: 2,
+            expected: Type::String,
+            given: Type::Null,
+        })
+    );
+    assert!(matches!(
+        validate("length(null)"),
+        Err(ValidationError::NoMatchingFunctionOverload { given, .. }) if given == [Type::Null]
+    ));
+}
+
+#[test]
+fn test_ordering_comparisons() {
+    let type_map = IdentifiersTypeMap::from(HashMap::from([
+        ("Name".to_string(), Type::String),
+        ("Age".to_string(), Type::Int32),
+        ("Born".to_string(), Type::Date),
+        ("CreatedAt".to_string(), Type::DateTime),
+        ("Opens".to_string(), Type::Time),
+        ("IsActive".to_string(), Type::Boolean),
+        ("Id".to_string(), Type::Uuid),
+        (
+            "Address".to_string(),
+            Type::Complex("Sales.Address".to_string()),
+        ),
+    ]));
+    let functions_map = FunctionsTypeMap::odata_standard();
+
+    let validate = |filter: &str| {
+        parse_str(filter)
+            .expect("valid filter tree")
+            .validate(&type_map, &functions_map)
+    };
+
+    assert_eq!(
+        validate("Name ge 'A' and Age lt 30.5 and Born gt 2000-01-01"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        validate("CreatedAt le now() and Opens lt 09:00:00"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        validate("IsActive ne false and Id eq da820b39-5ad2-4441-b664-c902dbd377d8"),
+        Ok(Type::Boolean)
+    );
+
+    assert_eq!(
+        validate("IsActive gt true"),
+        Err(ValidationError::UnsupportedComparisonOperator {
+            operator: CompareOperator::GreaterThan,
+            given: Type::Boolean,
+        })
+    );
+    assert_eq!(
+        validate("Id lt da820b39-5ad2-4441-b664-c902dbd377d8"),
+        Err(ValidationError::UnsupportedComparisonOperator {
+            operator: CompareOperator::LessThan,
+            given: Type::Uuid,
+        })
+    );
+    assert_eq!(
+        validate("Age le Address"),
+        Err(ValidationError::UnsupportedComparisonOperator {
+            operator: CompareOperator::LessOrEqual,
+            given: Type::Complex("Sales.Address".to_string()),
+        })
+    );
+
+    let error = validate("IsActive ge false").expect_err("booleans are unordered");
+    assert_eq!(
+        error.to_string(),
+        "Comparison operator 'ge' is not supported for type Boolean."
+    );
+
+    // Ordered types of different kinds are still incompatible.
+    assert_eq!(
+        validate("Name gt 5"),
+        Err(ValidationError::ComparingIncompatibleTypes {
+            lhs: Type::String,
+            rhs: Type::Int32,
+        })
+    );
+}
+
+#[test]
+fn test_duration_arithmetic() {
+    let type_map = IdentifiersTypeMap::from(HashMap::from([
+        ("CreatedAt".to_string(), Type::DateTime),
+        ("Born".to_string(), Type::Date),
+        ("Opens".to_string(), Type::Time),
+        ("Elapsed".to_string(), Type::Duration),
+        ("Age".to_string(), Type::Int32),
+    ]));
+    let functions_map = FunctionsTypeMap::odata_standard();
+
+    let validate = |filter: &str| {
+        parse_str(filter)
+            .expect("valid filter tree")
+            .validate(&type_map, &functions_map)
+    };
+
+    assert_eq!(
+        validate("CreatedAt gt now() sub duration'P7D'"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(validate("CreatedAt add PT1H"), Ok(Type::DateTime));
+    assert_eq!(validate("Born sub P1D"), Ok(Type::Date));
+    assert_eq!(validate("now() sub CreatedAt"), Ok(Type::Duration));
+    assert_eq!(validate("Born sub 2000-01-01"), Ok(Type::Duration));
+    assert_eq!(validate("Elapsed add PT30M"), Ok(Type::Duration));
+    assert_eq!(validate("Elapsed mul 2"), Ok(Type::Duration));
+    assert_eq!(validate("Age mul Elapsed"), Ok(Type::Duration));
+    assert_eq!(validate("Elapsed div 2.5"), Ok(Type::Duration));
+    assert_eq!(validate("-Elapsed"), Ok(Type::Duration));
+    assert_eq!(validate("totalseconds(Elapsed) gt 60"), Ok(Type::Boolean));
+    assert_eq!(validate("Elapsed ge PT1H"), Ok(Type::Boolean));
+
+    assert_eq!(
+        validate("CreatedAt add CreatedAt"),
+        Err(ValidationError::ArithmeticOnIncompatibleTypes {
+            operator: ArithmeticOperator::Add,
+            lhs: Type::DateTime,
+            rhs: Type::DateTime,
+        })
+    );
+    assert_eq!(
+        validate("Opens add PT1H"),
+        Err(ValidationError::ArithmeticOnIncompatibleTypes {
+            operator: ArithmeticOperator::Add,
+            lhs: Type::Time,
+            rhs: Type::Duration,
+        })
+    );
+    assert_eq!(
+        validate("2 div Elapsed"),
+        Err(ValidationError::ArithmeticOnIncompatibleTypes {
+            operator: ArithmeticOperator::Div,
+            lhs: Type::Int32,
+            rhs: Type::Duration,
+        })
+    );
+    assert_eq!(
+        validate("CreatedAt sub Born"),
+        Err(ValidationError::ArithmeticOnIncompatibleTypes {
+            operator: ArithmeticOperator::Sub,
+            lhs: Type::DateTime,
+            rhs: Type::Date,
+        })
+    );
+}
+
+#[test]
+fn test_collection_and_complex_values() {
+    let type_map = IdentifiersTypeMap::from(HashMap::from([
+        ("Name".to_string(), Type::String),
+        ("Tags".to_string(), Type::Collection(Box::new(Type::String))),
+        (
+            "Prices".to_string(),
+            Type::Collection(Box::new(Type::Decimal)),
+        ),
+        (
+            "Address".to_string(),
+            Type::Complex("Sales.Address".to_string()).nullable(),
+        ),
+    ]))
+    .with_complex_type(
+        "Sales.Address",
+        [
+            ("City", Type::String),
+            ("Zones", Type::Collection(Box::new(Type::Int64))),
+        ],
+    );
+    let functions_map = FunctionsTypeMap::default().with_function(
+        "hasany",
+        [
+            Type::Collection(Box::new(Type::String)),
+            Type::Collection(Box::new(Type::String)),
+        ],
+        None,
+        Type::Boolean,
+    );
+
+    let validate = |filter: &str| {
+        parse_str(filter)
+            .expect("valid filter tree")
+            .validate(&type_map, &functions_map)
+    };
+
+    assert_eq!(validate("Tags eq ['a','b']"), Ok(Type::Boolean));
+    assert_eq!(validate("Tags ne []"), Ok(Type::Boolean));
+    assert_eq!(validate("Prices eq [1, 2.5]"), Ok(Type::Boolean));
+    assert_eq!(validate("Name in ['a','b']"), Ok(Type::Boolean));
+    assert_eq!(validate("Name in Tags"), Ok(Type::Boolean));
+    assert_eq!(
+        validate("Name in Name"),
+        Err(ValidationError::InRequiresCollection {
+            given: Type::String
+        })
+    );
+    assert_eq!(
+        validate("Name in Prices"),
+        Err(ValidationError::ComparingIncompatibleTypes {
+            lhs: Type::String,
+            rhs: Type::Decimal,
+        })
+    );
+    assert_eq!(validate("hasany(Tags, ['a'])"), Ok(Type::Boolean));
+    assert_eq!(
+        validate(r#"Address eq {"City":"Montreal","Zones":[1,2]}"#),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        validate("[1, 2.5, null]"),
+        Ok(Type::Collection(Box::new(Type::Decimal.nullable())))
+    );
+    assert_eq!(
+        validate(r#"{"City":"Montreal"}"#),
+        Ok(Type::Object(vec![("City".to_string(), Type::String)]))
+    );
+
+    assert_eq!(
+        validate("[1, 'a']"),
+        Err(ValidationError::IncompatibleCollectionElements {
+            expected: Type::Int32,
+            given: Type::String,
+        })
+    );
+    assert_eq!(
+        validate("Tags eq [1]"),
+        Err(ValidationError::ComparingIncompatibleTypes {
+            lhs: Type::Collection(Box::new(Type::String)),
+            rhs: Type::Collection(Box::new(Type::Int32)),
+        })
+    );
+    assert_eq!(
+        validate("Tags gt ['a']"),
+        Err(ValidationError::UnsupportedComparisonOperator {
+            operator: CompareOperator::GreaterThan,
+            given: Type::Collection(Box::new(Type::String)),
+        })
+    );
+    assert_eq!(
+        validate(r#"Address eq {"Country":"CA"}"#),
+        Err(ValidationError::ComparingIncompatibleTypes {
+            lhs: Type::Complex("Sales.Address".to_string()),
+            rhs: Type::Object(vec![("Country".to_string(), Type::String)]),
+        })
+    );
+    assert_eq!(
+        validate("hasany(Tags, [1])"),
+        Err(ValidationError::IncorrectFunctionArgumentType {
+            name: "hasany".to_string(),
+            position: 2,
+            expected: Type::Collection(Box::new(Type::String)),
+            given: Type::Collection(Box::new(Type::Int32)),
+        })
+    );
+}
+
+#[test]
+fn test_spatial_functions() {
+    let type_map = IdentifiersTypeMap::from(HashMap::from([
+        ("Location".to_string(), Type::Geography),
+        ("Area".to_string(), Type::Geography),
+        ("Shape".to_string(), Type::Geometry),
+    ]));
+    let functions_map = FunctionsTypeMap::odata_standard();
+
+    let validate = |filter: &str| {
+        parse_str(filter)
+            .expect("valid filter tree")
+            .validate(&type_map, &functions_map)
+    };
+
+    assert_eq!(
+        validate("geo.distance(Location, geography'SRID=4326;POINT(-73.5 45.5)') lt 1000"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        validate("geo.intersects(Area, geography'POLYGON((0 0,1 0,1 1,0 0))')"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(validate("geo.length(Shape)"), Ok(Type::Double));
+    assert_eq!(validate("Shape eq geometry'POINT(1 2)'"), Ok(Type::Boolean));
+
+    assert!(matches!(
+        validate("geo.distance(Location, Shape)"),
+        Err(ValidationError::NoMatchingFunctionOverload { .. })
+    ));
+    assert_eq!(
+        validate("Location lt geography'POINT(1 2)'"),
+        Err(ValidationError::UnsupportedComparisonOperator {
+            operator: CompareOperator::LessThan,
+            given: Type::Geography,
+        })
+    );
+    assert_eq!(
+        validate("geo.area(Area)"),
+        Err(ValidationError::UndefinedFunction {
+            name: "geo.area".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_binary_and_bytes() {
+    let type_map = IdentifiersTypeMap::from(HashMap::from([
+        ("Data".to_string(), Type::Binary),
+        ("Flags".to_string(), Type::Byte),
+        ("Offset".to_string(), Type::SByte),
+        ("Age".to_string(), Type::Int32),
+    ]));
+    let functions_map = FunctionsTypeMap::odata_standard()
+        .with_function("shift", [Type::Byte], None, Type::Byte)
+        .with_function("pack", [Type::Byte], None, Type::Byte)
+        .with_function("pack", [Type::String], None, Type::Byte);
+
+    let validate = |filter: &str| {
+        parse_str(filter)
+            .expect("valid filter tree")
+            .validate(&type_map, &functions_map)
+    };
+
+    assert_eq!(validate("Data eq binary'T0RhdGE='"), Ok(Type::Boolean));
+    assert_eq!(validate("Flags eq 3 and Offset lt -1"), Ok(Type::Boolean));
+    assert_eq!(validate("Flags add Flags"), Ok(Type::Byte));
+    assert_eq!(validate("Flags add Offset"), Ok(Type::Int32));
+    assert_eq!(validate("Flags mul Age"), Ok(Type::Int32));
+    assert_eq!(validate("round(Flags)"), Ok(Type::Decimal));
+    assert_eq!(validate("shift(Flags)"), Ok(Type::Byte));
+
+    // Integer literals in range are used as bytes.
+    assert_eq!(validate("shift(5)"), Ok(Type::Byte));
+    assert_eq!(validate("shift(255)"), Ok(Type::Byte));
+    assert_eq!(
+        validate("Flags eq 255 and 0 eq Flags and Offset ge -128 and Flags in (1, 2)"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(validate("pack(7)"), Ok(Type::Byte));
+
+    // Integer literals out of range are rejected.
+    assert_eq!(
+        validate("shift(256)"),
+        Err(ValidationError::NumberOutOfRange {
+            value: BigDecimal::from(256),
+            expected: Type::Byte,
+        })
+    );
+    assert_eq!(
+        validate("Flags eq 300"),
+        Err(ValidationError::NumberOutOfRange {
+            value: BigDecimal::from(300),
+            expected: Type::Byte,
+        })
+    );
+    assert_eq!(
+        validate("Offset lt -129"),
+        Err(ValidationError::NumberOutOfRange {
+            value: BigDecimal::from(-129),
+            expected: Type::SByte,
+        })
+    );
+    assert_eq!(
+        validate("Flags in (1, -1)"),
+        Err(ValidationError::NumberOutOfRange {
+            value: BigDecimal::from(-1),
+            expected: Type::Byte,
+        })
+    );
+    assert!(matches!(
+        validate("pack(256)"),
+        Err(ValidationError::NoMatchingFunctionOverload { .. })
+    ));
+
+    assert_eq!(
+        validate("Data gt binary'T0RhdGE='"),
+        Err(ValidationError::UnsupportedComparisonOperator {
+            operator: CompareOperator::GreaterThan,
+            given: Type::Binary,
+        })
+    );
+    assert_eq!(
+        validate("Data eq 'OData'"),
+        Err(ValidationError::ComparingIncompatibleTypes {
+            lhs: Type::Binary,
+            rhs: Type::String,
+        })
+    );
+    assert_eq!(
+        validate("Flags eq 1.5"),
+        Err(ValidationError::ComparingIncompatibleTypes {
+            lhs: Type::Byte,
+            rhs: Type::Decimal,
+        })
+    );
+
+    // Signed bytes do not fit where bytes are expected, nor wider integers.
+    assert_eq!(
+        validate("shift(Offset)"),
+        Err(ValidationError::IncorrectFunctionArgumentType {
+            name: "shift".to_string(),
+            position: 1,
+            expected: Type::Byte,
+            given: Type::SByte,
+        })
+    );
+    assert_eq!(
+        validate("shift(Age)"),
+        Err(ValidationError::IncorrectFunctionArgumentType {
+            name: "shift".to_string(),
+            position: 1,
+            expected: Type::Byte,
+            given: Type::Int32,
+        })
+    );
+}
+
+#[test]
+fn test_type_functions() {
+    let type_map = IdentifiersTypeMap::from(HashMap::from([
+        ("Price".to_string(), Type::Int32),
+        ("Code".to_string(), Type::String.nullable()),
+        (
+            "Address".to_string(),
+            Type::Complex("Sales.Address".to_string()),
+        ),
+    ]))
+    .with_complex_type("Sales.Address", [("City", Type::String)])
+    .with_enum("Sales.Color", ["Red", "Green"]);
+    let functions_map = FunctionsTypeMap::default();
+
+    let validate = |filter: &str| {
+        parse_str(filter)
+            .expect("valid filter tree")
+            .validate(&type_map, &functions_map)
+    };
+
+    assert_eq!(
+        validate("cast(Price, Edm.String) eq '10'"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        validate("cast(Price, Edm.Int64) eq null"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        validate("cast(Code, Sales.Color) eq null"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(validate("cast(Edm.Decimal) gt 1"), Ok(Type::Boolean));
+    assert_eq!(
+        validate("isof(Address, Sales.Address) and isof(Edm.GeographyPoint)"),
+        Ok(Type::Boolean)
+    );
+
+    assert_eq!(
+        validate("isof(Price, Sales.Unknown)"),
+        Err(ValidationError::UndefinedType {
+            name: "Sales.Unknown".to_string(),
+        })
+    );
+    assert_eq!(
+        validate("cast(Missing, Edm.String)"),
+        Err(ValidationError::UndefinedIdentifier {
+            name: "Missing".to_string(),
+        })
+    );
+    assert_eq!(
+        Expr::TypeName("Edm.String".to_string()).validate(&type_map, &functions_map),
+        Err(ValidationError::UnexpectedTypeName {
+            name: "Edm.String".to_string(),
+        })
+    );
+}
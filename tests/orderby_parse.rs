@@ -0,0 +1,141 @@
+use odata_params::bigdecimal::BigDecimal;
+use odata_params::filters::CompareOperator::*;
+use odata_params::filters::{Expr, ParseError, Value};
+use odata_params::orderby::SortDirection::*;
+use odata_params::orderby::{parse_str, OrderByItem};
+
+#[test]
+fn single_identifier() {
+    let order_by = "name";
+    let result = parse_str(order_by).expect("valid order by list");
+
+    assert_eq!(
+        result,
+        vec![OrderByItem {
+            expr: Expr::Identifier("name".to_owned()),
+            direction: Ascending,
+        }]
+    );
+}
+
+#[test]
+fn explicit_directions() {
+    let order_by = "name desc, age asc";
+    let result = parse_str(order_by).expect("valid order by list");
+
+    assert_eq!(
+        result,
+        vec![
+            OrderByItem {
+                expr: Expr::Identifier("name".to_owned()),
+                direction: Descending,
+            },
+            OrderByItem {
+                expr: Expr::Identifier("age".to_owned()),
+                direction: Ascending,
+            },
+        ]
+    );
+}
+
+#[test]
+fn function_call_key() {
+    let order_by = "name desc,length(title)";
+    let result = parse_str(order_by).expect("valid order by list");
+
+    assert_eq!(
+        result,
+        vec![
+            OrderByItem {
+                expr: Expr::Identifier("name".to_owned()),
+                direction: Descending,
+            },
+            OrderByItem {
+                expr: Expr::Function(
+                    "length".to_owned(),
+                    vec![Expr::Identifier("title".to_owned())]
+                ),
+                direction: Ascending,
+            },
+        ]
+    );
+}
+
+#[test]
+fn comparison_key() {
+    let order_by = "age gt 30 desc";
+    let result = parse_str(order_by).expect("valid order by list");
+
+    assert_eq!(
+        result,
+        vec![OrderByItem {
+            expr: Expr::Compare(
+                Expr::Identifier("age".to_owned()).into(),
+                GreaterThan,
+                Expr::Value(Value::Number(BigDecimal::from(30))).into()
+            ),
+            direction: Descending,
+        }]
+    );
+}
+
+#[test]
+fn identifiers_starting_with_direction() {
+    let order_by = "description, ascii desc";
+    let result = parse_str(order_by).expect("valid order by list");
+
+    assert_eq!(
+        result,
+        vec![
+            OrderByItem {
+                expr: Expr::Identifier("description".to_owned()),
+                direction: Ascending,
+            },
+            OrderByItem {
+                expr: Expr::Identifier("ascii".to_owned()),
+                direction: Descending,
+            },
+        ]
+    );
+}
+
+#[test]
+fn member_path_key() {
+    let order_by = "address/city asc, name";
+    let result = parse_str(order_by).expect("valid order by list");
+
+    assert_eq!(
+        result,
+        vec![
+            OrderByItem {
+                expr: Expr::Path(vec!["address".to_owned(), "city".to_owned()]),
+                direction: Ascending,
+            },
+            OrderByItem {
+                expr: Expr::Identifier("name".to_owned()),
+                direction: Ascending,
+            },
+        ]
+    );
+}
+
+#[test]
+fn invalid_order_by() {
+    assert!(matches!(parse_str(""), Err(ParseError::Parsing { .. })));
+    assert!(matches!(
+        parse_str("name,"),
+        Err(ParseError::Parsing { .. })
+    ));
+    assert!(matches!(
+        parse_str("name sideways"),
+        Err(ParseError::Parsing { .. })
+    ));
+}
+
+#[test]
+fn error_within_sort_key() {
+    assert_eq!(
+        parse_str("length(").unwrap_err().to_string(),
+        "Error during general parsing at line 1, column 8, expected one of \"(\", \")\", \"-\", \"@\", identifier, literal, not.\nlength(\n       ^"
+    );
+}
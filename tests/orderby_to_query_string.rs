@@ -0,0 +1,51 @@
+use odata_params::bigdecimal::BigDecimal;
+use odata_params::filters::{CompareOperator, Expr, Value};
+use odata_params::orderby::{parse_str, to_query_string, OrderByItem, SortDirection};
+
+#[test]
+fn single_item() {
+    let items = vec![OrderByItem {
+        expr: Expr::Identifier("name".to_owned()),
+        direction: SortDirection::Ascending,
+    }];
+
+    let result = to_query_string(&items).expect("valid order by list");
+    assert_eq!(result, "name");
+}
+
+#[test]
+fn multiple_items() {
+    let items = vec![
+        OrderByItem {
+            expr: Expr::Identifier("name".to_owned()),
+            direction: SortDirection::Descending,
+        },
+        OrderByItem {
+            expr: Expr::Function(
+                "length".to_owned(),
+                vec![Expr::Identifier("title".to_owned())],
+            ),
+            direction: SortDirection::Ascending,
+        },
+        OrderByItem {
+            expr: Expr::Compare(
+                Box::new(Expr::Identifier("age".to_owned())),
+                CompareOperator::GreaterThan,
+                Box::new(Expr::Value(Value::Number(BigDecimal::from(30)))),
+            ),
+            direction: SortDirection::Descending,
+        },
+    ];
+
+    let result = to_query_string(&items).expect("valid order by list");
+    assert_eq!(result, "name desc, length(title), age gt 30 desc");
+}
+
+#[test]
+fn round_trip() {
+    let order_by = "name desc, length(title), concat(city, country) desc";
+    let items = parse_str(order_by).expect("valid order by list");
+
+    let result = to_query_string(&items).expect("valid order by list");
+    assert_eq!(result, order_by);
+}
@@ -0,0 +1,118 @@
+use odata_params::pagination::{
+    parse_count, parse_skip, parse_top, PageSizePolicy, Pagination, PaginationError,
+};
+
+#[test]
+fn top_and_skip_values() {
+    assert_eq!(parse_top("0"), Ok(0));
+    assert_eq!(parse_top("25"), Ok(25));
+    assert_eq!(parse_skip(" 100 "), Ok(100));
+}
+
+#[test]
+fn negative_values() {
+    assert_eq!(
+        parse_top("-5"),
+        Err(PaginationError::NegativeTop {
+            value: "-5".to_owned()
+        })
+    );
+    assert_eq!(
+        parse_skip("-10"),
+        Err(PaginationError::NegativeSkip {
+            value: "-10".to_owned()
+        })
+    );
+}
+
+#[test]
+fn non_integer_values() {
+    for value in [
+        "",
+        "abc",
+        "1.5",
+        "+3",
+        "1e3",
+        "-",
+        "-1.5",
+        "99999999999999999999",
+    ] {
+        assert_eq!(
+            parse_top(value),
+            Err(PaginationError::InvalidTop {
+                value: value.to_owned()
+            })
+        );
+        assert_eq!(
+            parse_skip(value),
+            Err(PaginationError::InvalidSkip {
+                value: value.to_owned()
+            })
+        );
+    }
+}
+
+#[test]
+fn count_values() {
+    assert_eq!(parse_count("true"), Ok(true));
+    assert_eq!(parse_count("True"), Ok(true));
+    assert_eq!(parse_count("false"), Ok(false));
+    assert_eq!(
+        parse_count("1"),
+        Err(PaginationError::InvalidCount {
+            value: "1".to_owned()
+        })
+    );
+}
+
+#[test]
+fn all_options() {
+    assert_eq!(
+        Pagination::parse(Some("10"), Some("30"), Some("true")),
+        Ok(Pagination {
+            top: Some(10),
+            skip: Some(30),
+            count: true,
+        })
+    );
+    assert_eq!(
+        Pagination::parse(None, None, None),
+        Ok(Pagination::default())
+    );
+    assert_eq!(
+        Pagination::parse(Some("10"), Some("x"), None),
+        Err(PaginationError::InvalidSkip {
+            value: "x".to_owned()
+        })
+    );
+}
+
+#[test]
+fn page_size_policy() {
+    let policy = PageSizePolicy {
+        default_page_size: Some(20),
+        max_page_size: Some(50),
+    };
+
+    let pagination = Pagination::parse(None, Some("40"), None).expect("valid pagination");
+    assert_eq!(
+        policy.apply(pagination),
+        Ok(Pagination {
+            top: Some(20),
+            skip: Some(40),
+            count: false,
+        })
+    );
+
+    let pagination = Pagination::parse(Some("50"), None, None).expect("valid pagination");
+    assert_eq!(policy.apply(pagination), Ok(pagination));
+
+    let pagination = Pagination::parse(Some("51"), None, None).expect("valid pagination");
+    assert_eq!(
+        policy.apply(pagination),
+        Err(PaginationError::TopExceedsMaxPageSize { given: 51, max: 50 })
+    );
+
+    let pagination = Pagination::parse(None, None, None).expect("valid pagination");
+    assert_eq!(PageSizePolicy::default().apply(pagination), Ok(pagination));
+}
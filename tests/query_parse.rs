@@ -0,0 +1,246 @@
+use odata_params::bigdecimal::BigDecimal;
+use odata_params::expand::ExpandError;
+use odata_params::filters::CompareOperator::*;
+use odata_params::filters::{AliasError, Expr, ParseError, Value};
+use odata_params::orderby::{OrderByItem, SortDirection};
+use odata_params::pagination::{Pagination, PaginationError};
+use odata_params::query::{parse_str, percent_decode, QueryError};
+use odata_params::select::SelectItem;
+use std::collections::HashMap;
+
+#[test]
+fn all_parsed_options() {
+    let query = "?$filter=age%20gt%2030&$orderby=name%20desc&$top=10&$skip=20&$count=true";
+    let result = parse_str(query).expect("valid query options");
+
+    assert_eq!(
+        result.filter,
+        Some(Expr::Compare(
+            Expr::Identifier("age".to_owned()).into(),
+            GreaterThan,
+            Expr::Value(Value::Number(BigDecimal::from(30))).into()
+        ))
+    );
+    assert_eq!(
+        result.order_by,
+        Some(vec![OrderByItem {
+            expr: Expr::Identifier("name".to_owned()),
+            direction: SortDirection::Descending,
+        }])
+    );
+    assert_eq!(
+        result.pagination,
+        Pagination {
+            top: Some(10),
+            skip: Some(20),
+            count: true,
+        }
+    );
+    assert!(result.other_options.is_empty());
+    assert!(result.custom.is_empty());
+}
+
+#[test]
+fn empty_query() {
+    let result = parse_str("").expect("valid query options");
+
+    assert_eq!(result, Default::default());
+}
+
+#[test]
+fn case_insensitive_and_optional_prefix() {
+    let result =
+        parse_str("FILTER=isActive&$Top=5&$SELECT=Name&$Search=blue").expect("valid query options");
+
+    assert_eq!(result.filter, Some(Expr::Identifier("isActive".to_owned())));
+    assert_eq!(result.pagination.top, Some(5));
+    assert_eq!(
+        result.select,
+        Some(vec![SelectItem::Path(vec!["Name".to_owned()])])
+    );
+    assert_eq!(
+        result.other_options.get("search").map(String::as_str),
+        Some("blue")
+    );
+}
+
+#[test]
+fn custom_options() {
+    let result = parse_str("$top=1&api-version=2&debug&Tag=a%26b").expect("valid query options");
+
+    assert_eq!(
+        result.custom,
+        vec![
+            ("api-version".to_owned(), "2".to_owned()),
+            ("debug".to_owned(), "".to_owned()),
+            ("Tag".to_owned(), "a&b".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn unprefixed_custom_options() {
+    let result =
+        parse_str("id=5&format=json&index=2&$format=xml&top=3").expect("valid query options");
+
+    assert_eq!(
+        result.custom,
+        vec![
+            ("id".to_owned(), "5".to_owned()),
+            ("format".to_owned(), "json".to_owned()),
+            ("index".to_owned(), "2".to_owned()),
+        ]
+    );
+    assert_eq!(
+        result.other_options.get("format").map(String::as_str),
+        Some("xml")
+    );
+    assert_eq!(result.pagination.top, Some(3));
+}
+
+#[test]
+fn prefix_of_unparsed_system_options() {
+    let result = parse_str("$search=blue&$SkipToken=abc").expect("valid query options");
+    assert_eq!(
+        result.other_options,
+        HashMap::from([
+            ("search".to_owned(), "blue".to_owned()),
+            ("skiptoken".to_owned(), "abc".to_owned()),
+        ])
+    );
+    assert!(result.custom.is_empty());
+
+    let result = parse_str("search=blue&SkipToken=abc").expect("valid query options");
+    assert!(result.other_options.is_empty());
+    assert_eq!(
+        result.custom,
+        vec![
+            ("search".to_owned(), "blue".to_owned()),
+            ("SkipToken".to_owned(), "abc".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn duplicate_options() {
+    assert_eq!(
+        parse_str("$top=1&$top=2"),
+        Err(QueryError::DuplicateOption {
+            name: "top".to_owned()
+        })
+    );
+    assert_eq!(
+        parse_str("$filter=a&Filter=b"),
+        Err(QueryError::DuplicateOption {
+            name: "filter".to_owned()
+        })
+    );
+}
+
+#[test]
+fn unknown_system_option() {
+    assert_eq!(
+        parse_str("$foo=bar"),
+        Err(QueryError::UnknownSystemOption {
+            name: "foo".to_owned()
+        })
+    );
+}
+
+#[test]
+fn option_errors() {
+    assert!(matches!(
+        parse_str("$filter=name eq"),
+        Err(QueryError::Filter(ParseError::Parsing { .. }))
+    ));
+    assert!(matches!(
+        parse_str("$orderby=name sideways"),
+        Err(QueryError::OrderBy(ParseError::Parsing { .. }))
+    ));
+    assert!(matches!(
+        parse_str("$select=Name,"),
+        Err(QueryError::Select(ParseError::Parsing { .. }))
+    ));
+    assert!(matches!(
+        parse_str("$expand=Orders($top=x)"),
+        Err(QueryError::Expand(ExpandError::Parse(
+            ParseError::Parsing { .. }
+        )))
+    ));
+    assert_eq!(
+        parse_str("$top=-1"),
+        Err(QueryError::Pagination(PaginationError::NegativeTop {
+            value: "-1".to_owned()
+        }))
+    );
+}
+
+#[test]
+fn percent_decoding() {
+    assert_eq!(percent_decode("a%2Fb%2fc"), Ok("a/b/c".to_owned()));
+    assert_eq!(percent_decode("1+2"), Ok("1+2".to_owned()));
+    assert_eq!(
+        percent_decode("100%"),
+        Err(QueryError::InvalidPercentEncoding {
+            input: "100%".to_owned()
+        })
+    );
+    assert_eq!(
+        percent_decode("%zz"),
+        Err(QueryError::InvalidPercentEncoding {
+            input: "%zz".to_owned()
+        })
+    );
+    assert_eq!(
+        percent_decode("%FF"),
+        Err(QueryError::InvalidPercentEncoding {
+            input: "%FF".to_owned()
+        })
+    );
+}
+
+#[test]
+fn parameter_aliases() {
+    let query = "$filter=Price gt @minPrice&$orderby=@sortKey desc&$expand=Orders($filter=Total gt @minPrice)&@minPrice=10&@sortKey=Name&debug";
+    let result = parse_str(query).expect("valid query options");
+
+    let min_price = Expr::Compare(
+        Expr::Identifier("Price".to_owned()).into(),
+        GreaterThan,
+        Expr::Value(Value::Number(BigDecimal::from(10))).into(),
+    );
+    assert_eq!(result.filter, Some(min_price));
+    assert_eq!(
+        result.order_by,
+        Some(vec![OrderByItem {
+            expr: Expr::Identifier("Name".to_owned()),
+            direction: SortDirection::Descending,
+        }])
+    );
+    assert_eq!(
+        result.expand.unwrap()[0].options.filter,
+        Some(Expr::Compare(
+            Expr::Identifier("Total".to_owned()).into(),
+            GreaterThan,
+            Expr::Value(Value::Number(BigDecimal::from(10))).into(),
+        ))
+    );
+    assert_eq!(result.aliases.len(), 2);
+    assert_eq!(result.custom, vec![("debug".to_owned(), "".to_owned())]);
+}
+
+#[test]
+fn parameter_alias_errors() {
+    assert_eq!(
+        parse_str("$filter=Price gt @minPrice"),
+        Err(QueryError::Alias(AliasError::UndefinedAlias {
+            name: "minPrice".to_owned()
+        }))
+    );
+    assert_eq!(
+        parse_str("@p=1&@p=2"),
+        Err(QueryError::DuplicateAlias {
+            name: "p".to_owned()
+        })
+    );
+}
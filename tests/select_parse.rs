@@ -0,0 +1,56 @@
+use odata_params::filters::ParseError;
+use odata_params::select::{parse_str, to_query_string, SelectItem};
+
+#[test]
+fn single_property() {
+    let result = parse_str("Name").expect("valid select list");
+
+    assert_eq!(result, vec![SelectItem::Path(vec!["Name".to_owned()])]);
+}
+
+#[test]
+fn nested_paths_and_wildcards() {
+    let select = "Name, Address/City,*,Namespace.*,My.Long.Namespace.*";
+    let result = parse_str(select).expect("valid select list");
+
+    assert_eq!(
+        result,
+        vec![
+            SelectItem::Path(vec!["Name".to_owned()]),
+            SelectItem::Path(vec!["Address".to_owned(), "City".to_owned()]),
+            SelectItem::Wildcard,
+            SelectItem::NamespaceWildcard("Namespace".to_owned()),
+            SelectItem::NamespaceWildcard("My.Long.Namespace".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn invalid_select() {
+    assert!(matches!(parse_str(""), Err(ParseError::Parsing { .. })));
+    assert!(matches!(
+        parse_str("Name,"),
+        Err(ParseError::Parsing { .. })
+    ));
+    assert!(matches!(
+        parse_str("Address/"),
+        Err(ParseError::Parsing { .. })
+    ));
+    assert!(matches!(
+        parse_str("Address/*/City"),
+        Err(ParseError::Parsing { .. })
+    ));
+    assert!(matches!(
+        parse_str("Namespace."),
+        Err(ParseError::Parsing { .. })
+    ));
+}
+
+#[test]
+fn round_trip() {
+    let select = "Name,Address/City,*,Namespace.*";
+    let items = parse_str(select).expect("valid select list");
+
+    let result = to_query_string(&items).expect("valid select list");
+    assert_eq!(result, select);
+}
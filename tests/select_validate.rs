@@ -0,0 +1,60 @@
+use odata_params::filters::{IdentifiersTypeMap, Type, ValidationError};
+use odata_params::select::{parse_str, validate, SelectItem};
+use std::collections::HashMap;
+
+fn identifiers() -> IdentifiersTypeMap {
+    let mut id_map = HashMap::new();
+    id_map.insert("Name".to_string(), Type::String);
+    id_map.insert(
+        "Address".to_string(),
+        Type::Complex("Sales.Address".to_string()),
+    );
+    IdentifiersTypeMap::from(id_map).with_complex_type("Sales.Address", [("City", Type::String)])
+}
+
+#[test]
+fn known_properties() {
+    let items = parse_str("Name,Address/City,*,Namespace.*").expect("valid select list");
+
+    assert_eq!(validate(&items, &identifiers()), Ok(()));
+}
+
+#[test]
+fn unknown_properties() {
+    let items = parse_str("Name,Age").expect("valid select list");
+    assert_eq!(
+        validate(&items, &identifiers()),
+        Err(ValidationError::UndefinedIdentifier {
+            name: "Age".to_owned()
+        })
+    );
+
+    let item = SelectItem::Path(vec!["Location".to_owned(), "City".to_owned()]);
+    assert_eq!(
+        item.validate(&identifiers()),
+        Err(ValidationError::UndefinedIdentifier {
+            name: "Location".to_owned()
+        })
+    );
+}
+
+#[test]
+fn unknown_nested_properties() {
+    let items = parse_str("Address/Country").expect("valid select list");
+    assert_eq!(
+        validate(&items, &identifiers()),
+        Err(ValidationError::UndefinedProperty {
+            type_name: "Sales.Address".to_owned(),
+            property: "Country".to_owned()
+        })
+    );
+
+    let items = parse_str("Name/First").expect("valid select list");
+    assert_eq!(
+        validate(&items, &identifiers()),
+        Err(ValidationError::PropertyAccessRequiresComplexType {
+            property: "First".to_owned(),
+            given: Type::String
+        })
+    );
+}